@@ -1,6 +1,7 @@
 use crate::input::Input;
 use crate::output::{Output, OutputType};
-use std::io::{Read, Stdin, Stdout, Write};
+use std::io::{stdin, stdout, Read, Stdin, Stdout, Write};
+use std::time::{Duration, Instant};
 
 pub trait Solver: Sized {
     type Result;
@@ -10,11 +11,31 @@ pub trait Solver: Sized {
 pub struct Atcoder<R: Read, W: Write> {
     input: Input<R>,
     output: Output<W>,
+    start: Instant,
+    time_limit: Duration,
 }
 
 impl<R: Read, W: Write> Atcoder<R, W> {
     pub fn with_io(input: Input<R>, output: Output<W>) -> Self {
-        Self { input, output }
+        Self {
+            input,
+            output,
+            start: Instant::now(),
+            time_limit: Duration::from_secs(2),
+        }
+    }
+
+    pub fn set_time_limit(&mut self, limit: Duration) {
+        self.time_limit = limit;
+    }
+
+    /// Time left before the limit (2 seconds unless set), counted from construction.
+    pub fn time_remaining(&self) -> Duration {
+        self.time_limit.saturating_sub(self.start.elapsed())
+    }
+
+    pub fn io(&mut self) -> (&mut Input<R>, &mut Output<W>) {
+        (&mut self.input, &mut self.output)
     }
 
     pub fn run<T: Solver<Result = impl Into<OutputType>>>(&mut self, solver: T) {
@@ -22,13 +43,212 @@ impl<R: Read, W: Write> Atcoder<R, W> {
         let result = solver.solve::<R>(&mut self.input).into();
         self.output.write(result);
     }
+
+    pub fn run_with(&mut self, solve: impl FnOnce(&mut Input<R>, &mut Output<W>)) {
+        solve(&mut self.input, &mut self.output);
+    }
+
+    /// Like `run`, but reports the time spent solving on stderr and returns it.
+    pub fn run_timed<T: Solver<Result = impl Into<OutputType>>>(&mut self, solver: T) -> Duration {
+        let start = Instant::now();
+        self.run(solver);
+        let elapsed = start.elapsed();
+        eprintln!("elapsed: {:?}", elapsed);
+        elapsed
+    }
+
+    pub fn run_multi<T: Solver<Result = impl Into<OutputType>>>(&mut self, make: impl Fn() -> T) {
+        let cases = self.input.read::<usize>();
+        for _ in 0..cases {
+            self.run(make());
+        }
+    }
+}
+
+impl Atcoder<Stdin, Stdout> {
+    pub fn interactive() -> Self {
+        Self::with_io(Input::interactive(stdin()), Output::interactive(stdout()))
+    }
 }
 
 impl Default for Atcoder<Stdin, Stdout> {
     fn default() -> Self {
-        Self {
-            input: Input::<Stdin>::default(),
-            output: Output::<Stdout>::default(),
+        Self::with_io(Input::<Stdin>::default(), Output::<Stdout>::default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod atcoder {
+        use super::super::{Atcoder, Solver};
+        use crate::input::Input;
+        use crate::output::{Output, OutputType};
+        use std::io::{Read, Write};
+        use std::sync::mpsc::{channel, Receiver, Sender};
+        use std::thread;
+        use std::time::Duration;
+
+        struct Reader(Receiver<Vec<u8>>, Vec<u8>);
+
+        impl Read for Reader {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                if self.1.is_empty() {
+                    match self.0.recv() {
+                        Ok(bytes) => self.1 = bytes,
+                        Err(_) => return Ok(0),
+                    }
+                }
+                let len = buf.len().min(self.1.len());
+                buf[..len].copy_from_slice(&self.1[..len]);
+                self.1.drain(..len);
+                Ok(len)
+            }
+        }
+
+        struct Writer(Sender<Vec<u8>>);
+
+        impl Write for Writer {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.send(buf.to_vec()).unwrap();
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        struct Double;
+
+        impl Solver for Double {
+            type Result = i64;
+
+            fn solve<T: Read>(&mut self, input: &mut Input<T>) -> i64 {
+                input.read::<i64>() * 2
+            }
+        }
+
+        #[test]
+        fn test_run() {
+            let mut buf = vec![];
+            let mut atcoder = Atcoder::with_io(Input::new("21".as_bytes()), Output::new(&mut buf));
+            atcoder.run(Double);
+            drop(atcoder);
+            assert_eq!(b"42\n".to_vec(), buf);
+        }
+
+        #[test]
+        fn test_run_multi() {
+            let mut buf = vec![];
+            let mut atcoder =
+                Atcoder::with_io(Input::new("3\n1\n2\n3".as_bytes()), Output::new(&mut buf));
+            atcoder.run_multi(|| Double);
+            drop(atcoder);
+            assert_eq!("2\n4\n6\n", String::from_utf8(buf).unwrap());
+
+            let mut buf = vec![];
+            let mut atcoder = Atcoder::with_io(Input::new("0".as_bytes()), Output::new(&mut buf));
+            atcoder.run_multi(|| Double);
+            drop(atcoder);
+            assert!(buf.is_empty());
+        }
+
+        #[test]
+        fn test_run_with() {
+            let mut buf = vec![];
+            let mut atcoder =
+                Atcoder::with_io(Input::new("3\n4 5 6".as_bytes()), Output::new(&mut buf));
+            atcoder.run_with(|input, output| {
+                let n = input.read::<usize>();
+                for _ in 0..n {
+                    let x = input.read::<i64>();
+                    output.write(OutputType::line(vec![x, x * x]));
+                }
+                output.write(OutputType::from(true));
+            });
+            drop(atcoder);
+            assert_eq!("4 16\n5 25\n6 36\nYes\n", String::from_utf8(buf).unwrap());
+        }
+
+        struct Slow;
+
+        impl Solver for Slow {
+            type Result = i64;
+
+            fn solve<T: Read>(&mut self, input: &mut Input<T>) -> i64 {
+                thread::sleep(Duration::from_millis(20));
+                input.read()
+            }
+        }
+
+        #[test]
+        fn test_run_timed() {
+            let mut buf = vec![];
+            let mut atcoder = Atcoder::with_io(Input::new("7".as_bytes()), Output::new(&mut buf));
+            let elapsed = atcoder.run_timed(Slow);
+            assert!(elapsed >= Duration::from_millis(20));
+            drop(atcoder);
+            assert_eq!(b"7\n".to_vec(), buf);
+        }
+
+        #[test]
+        fn test_time_remaining() {
+            let mut buf = vec![];
+            let mut atcoder = Atcoder::with_io(Input::new("".as_bytes()), Output::new(&mut buf));
+            assert!(atcoder.time_remaining() <= Duration::from_secs(2));
+            assert!(atcoder.time_remaining() > Duration::from_secs(1));
+            atcoder.set_time_limit(Duration::from_millis(10));
+            thread::sleep(Duration::from_millis(20));
+            assert_eq!(Duration::from_secs(0), atcoder.time_remaining());
+        }
+
+        fn pipe() -> (Reader, Writer) {
+            let (sender, receiver) = channel();
+            (Reader(receiver, vec![]), Writer(sender))
+        }
+
+        #[test]
+        fn test_interactive() {
+            let (judge_reader, solver_writer) = pipe();
+            let (solver_reader, judge_writer) = pipe();
+            let judge = thread::spawn(move || {
+                let answer = 37;
+                let mut input = Input::interactive(judge_reader);
+                let mut output = Output::interactive(judge_writer);
+                output.write(OutputType::from(100));
+                for count in 1.. {
+                    let guess = input.read::<i32>();
+                    if guess == answer {
+                        output.write(OutputType::from("=".to_string()));
+                        return count;
+                    } else if guess < answer {
+                        output.write(OutputType::from(">".to_string()));
+                    } else {
+                        output.write(OutputType::from("<".to_string()));
+                    }
+                }
+                unreachable!()
+            });
+
+            let mut atcoder = Atcoder::with_io(
+                Input::interactive(solver_reader),
+                Output::interactive(solver_writer),
+            );
+            let (input, output) = atcoder.io();
+            let (mut low, mut high) = (1, input.read::<i32>());
+            let mut count = 0;
+            loop {
+                let guess = (low + high) / 2;
+                output.write(OutputType::from(guess));
+                count += 1;
+                match input.read::<String>().as_str() {
+                    "=" => break,
+                    ">" => low = guess + 1,
+                    _ => high = guess - 1,
+                }
+            }
+            assert_eq!(count, judge.join().unwrap());
+            assert!(count <= 7);
         }
     }
 }
@@ -1,6 +1,51 @@
-use crate::group::{Abelian, Group};
-use std::{fmt::Debug, ops::RangeBounds};
+use crate::group::{Abelian, Group, Monoid};
+use crate::range::{bounds, try_bounds};
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::RangeBounds;
 
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum RangeError {
+    Empty,
+    OutOfBounds,
+    Inverted,
+}
+
+impl Display for RangeError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "range is empty"),
+            Self::OutOfBounds => write!(f, "range is out of bounds"),
+            Self::Inverted => write!(f, "range start is past its end"),
+        }
+    }
+}
+
+impl std::error::Error for RangeError {}
+
+fn up(index: usize) -> usize {
+    index + ((index + 1) & !index)
+}
+
+/// Fenwick tree over an abelian group, supporting arbitrary range queries.
+///
+/// ```
+/// use competitive::binary_indexed_tree::BIT;
+/// use competitive::group::Sum;
+///
+/// let mut bit = BIT::<Sum<i64>>::new(4);
+/// bit.add(1, 3);
+/// bit.add(3, 5);
+/// assert_eq!(Sum(8), bit.query(1..4));
+/// ```
+///
+/// Monoids without inverses are rejected at compile time; use `MonoidBIT` for them.
+///
+/// ```compile_fail
+/// use competitive::binary_indexed_tree::BIT;
+/// use competitive::group::Max;
+///
+/// let bit = BIT::<Max<i64>>::new(4);
+/// ```
 #[derive(Eq, PartialEq, Clone, Default, Debug)]
 pub struct BIT<T: Abelian + Group> {
     tree: Vec<T>,
@@ -23,8 +68,8 @@ impl<T: Abelian + Group> BIT<T> {
         self.tree.len()
     }
 
-    fn up(index: usize) -> usize {
-        index + ((index + 1) & !index)
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
     }
 
     pub fn add(&mut self, index: usize, value: impl Into<T>) {
@@ -33,7 +78,7 @@ impl<T: Abelian + Group> BIT<T> {
         let value = value.into();
         while index < self.len() {
             self.tree[index] = self.tree[index].apply(&value);
-            index = Self::up(index);
+            index = up(index);
         }
     }
 
@@ -43,21 +88,27 @@ impl<T: Abelian + Group> BIT<T> {
     }
 
     pub fn query(&self, range: impl RangeBounds<usize>) -> T {
-        use std::ops::Bound::*;
+        self.try_query(range).unwrap()
+    }
 
+    pub fn try_query(&self, range: impl RangeBounds<usize>) -> Result<T, RangeError> {
         let len = self.len();
-        let begin = match range.start_bound() {
-            Unbounded => 0,
-            Included(&b) => b,
-            Excluded(&b) => b + 1,
-        };
-        let end = match range.end_bound() {
-            Unbounded => len,
-            Included(&e) => e + 1,
-            Excluded(&e) => e,
-        };
-        assert!(begin < end && begin < len && end <= len);
-        self.sum(end).apply(&self.sum(begin).inverse())
+        let (begin, end) = try_bounds(range, len).ok_or(RangeError::OutOfBounds)?;
+        if begin > end {
+            return Err(RangeError::Inverted);
+        }
+        if end > len {
+            return Err(RangeError::OutOfBounds);
+        }
+        if begin == end {
+            return Err(RangeError::Empty);
+        }
+        Ok(self.sum(end).apply(&self.sum(begin).inverse()))
+    }
+
+    /// Aggregate over `0..end`; unlike `query`, `end == 0` yields the identity.
+    pub fn prefix(&self, end: usize) -> T {
+        self.sum(end)
     }
 
     fn down(index: usize) -> Option<usize> {
@@ -83,12 +134,65 @@ impl<T: Abelian + Group> BIT<T> {
     }
 }
 
+/// Fenwick tree over a commutative monoid. Without inverses only prefix ranges
+/// (`..end`, `..=last`, `0..end`) can be queried, and `add` combines into the existing value.
+#[derive(Clone, Default, Debug)]
+pub struct MonoidBIT<M: Monoid> {
+    tree: Vec<M>,
+}
+
+impl<M: Monoid + Clone> MonoidBIT<M> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            tree: vec![M::identity(); n],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    pub fn add(&mut self, index: usize, value: impl Into<M>) {
+        assert!(index < self.len());
+        let mut index = index;
+        let value = value.into();
+        while index < self.len() {
+            self.tree[index] = self.tree[index].apply(&value);
+            index = up(index);
+        }
+    }
+
+    pub fn query(&self, range: impl RangeBounds<usize>) -> M {
+        let (begin, end) = bounds(range, self.len());
+        assert!(begin == 0, "MonoidBIT only supports prefix queries");
+        self.prefix(end)
+    }
+
+    pub fn prefix(&self, end: usize) -> M {
+        assert!(end <= self.len());
+        let mut ret = M::identity();
+        let mut index = end;
+        while index > 0 {
+            ret = ret.apply(&self.tree[index - 1]);
+            index &= index - 1;
+        }
+        ret
+    }
+}
+
 #[cfg(test)]
 mod test {
 
     mod bit {
-        use super::super::BIT;
-        use crate::group::Sum;
+        use super::super::{RangeError, BIT};
+        use crate::group::{KahanSum, Sum};
+        use rand::rngs::SmallRng;
+        use rand::{Rng, SeedableRng};
+        use std::ops::Bound;
 
         #[test]
         fn test_len() {
@@ -145,5 +249,171 @@ mod test {
             assert_eq!(15, bit.query(..=5).0);
             assert_eq!(35, bit.query(2..=8).0);
         }
+
+        #[test]
+        fn test_f64() {
+            let n = 100000;
+            let mut bit = BIT::<Sum<f64>>::new(n);
+            for i in 0..n {
+                bit.add(i, 0.1);
+            }
+            assert!((bit.query(..).0 - 10000.0).abs() < 1e-6);
+            assert!((bit.query(500..1500).0 - 100.0).abs() < 1e-6);
+            assert!((bit.get(n - 1).0 - 0.1).abs() < 1e-9);
+        }
+
+        #[test]
+        fn test_kahan_sum() {
+            let n = 100000;
+            let mut bit = BIT::<KahanSum>::new(n);
+            bit.add(0, 1e8);
+            for i in 1..n {
+                bit.add(i, 0.1);
+            }
+            assert!((bit.query(1..).value() - 9999.9).abs() < 1e-9);
+            assert!((bit.query(n - 10..).value() - 1.0).abs() < 1e-12);
+            assert!((bit.get(n - 1).value() - 0.1).abs() < 1e-12);
+        }
+
+        #[test]
+        fn test_unsigned() {
+            let mut rng = SmallRng::seed_from_u64(855);
+            let n = 50;
+            let mut data = vec![0u64; n];
+            let mut bit = BIT::<Sum<u64>>::new(n);
+            for _ in 0..200 {
+                let i = rng.gen_range(0, n);
+                let x = rng.gen_range(0, 1u64 << 40);
+                data[i] += x;
+                bit.add(i, x);
+            }
+            for l in 0..n {
+                for r in l + 1..=n {
+                    assert_eq!(data[l..r].iter().sum::<u64>(), bit.query(l..r).0);
+                }
+            }
+
+            let mut bit = BIT::<Sum<u8>>::new(3);
+            bit.add(0, 200u8);
+            bit.add(1, 100u8);
+            bit.add(2, 50u8);
+            assert_eq!(150, bit.query(1..).0);
+            assert_eq!(100, bit.get(1).0);
+        }
+
+        #[test]
+        #[allow(clippy::reversed_empty_ranges)]
+        fn test_try_query() {
+            let mut bit = BIT::<Sum<i64>>::new(5);
+            for i in 0..5 {
+                bit.add(i, i as i64 + 1);
+            }
+            assert_eq!(Ok(Sum(15)), bit.try_query(..));
+            assert_eq!(Ok(Sum(9)), bit.try_query(1..=3));
+            assert_eq!(Ok(Sum(5)), bit.try_query(4..5));
+            assert_eq!(Err(RangeError::Empty), bit.try_query(5..5));
+            assert_eq!(Err(RangeError::Empty), bit.try_query(2..2));
+            assert_eq!(Err(RangeError::Empty), bit.try_query(5..));
+            assert_eq!(Err(RangeError::OutOfBounds), bit.try_query(3..6));
+            assert_eq!(Err(RangeError::OutOfBounds), bit.try_query(..=5));
+            assert_eq!(Err(RangeError::OutOfBounds), bit.try_query(..=usize::MAX));
+            assert_eq!(
+                Err(RangeError::OutOfBounds),
+                bit.try_query((Bound::Excluded(usize::MAX), Bound::Unbounded))
+            );
+            assert_eq!(Err(RangeError::Inverted), bit.try_query(3..1));
+            assert_eq!(Err(RangeError::Inverted), bit.try_query(7..6));
+            assert_eq!(
+                Err(RangeError::Empty),
+                BIT::<Sum<i64>>::new(0).try_query(..)
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        fn test_query_empty() {
+            BIT::<Sum<i64>>::new(5).query(2..2);
+        }
+
+        #[test]
+        fn test_prefix() {
+            let mut rng = SmallRng::seed_from_u64(871);
+            let n = 64;
+            let mut bit = BIT::<Sum<i64>>::new(n);
+            for _ in 0..100 {
+                bit.add(rng.gen_range(0, n), rng.gen_range(-50, 50));
+            }
+            assert_eq!(0, bit.prefix(0).0);
+            for k in 1..=n {
+                assert_eq!(bit.query(..k).0, bit.prefix(k).0);
+            }
+        }
+
+        #[test]
+        #[should_panic]
+        fn test_prefix_out_of_bounds() {
+            BIT::<Sum<i64>>::new(3).prefix(4);
+        }
+    }
+
+    mod monoid_bit {
+        use super::super::MonoidBIT;
+        use crate::group::{Max, Min};
+        use rand::rngs::SmallRng;
+        use rand::{Rng, SeedableRng};
+
+        #[test]
+        fn test_prefix_max() {
+            let mut rng = SmallRng::seed_from_u64(856);
+            let n = 40;
+            let mut data = vec![i64::MIN; n];
+            let mut bit = MonoidBIT::<Max<i64>>::new(n);
+            for _ in 0..200 {
+                let i = rng.gen_range(0, n);
+                let x = rng.gen_range(-1000, 1000);
+                data[i] = data[i].max(x);
+                bit.add(i, x);
+                for end in 0..=n {
+                    let expected = data[..end].iter().copied().fold(i64::MIN, i64::max);
+                    assert_eq!(expected, bit.query(..end).0);
+                }
+            }
+            assert_eq!(data.iter().max().copied().unwrap(), bit.query(..).0);
+            assert_eq!(data[0], bit.query(0..=0).0);
+        }
+
+        #[test]
+        fn test_prefix_min() {
+            let mut bit = MonoidBIT::<Min<u32>>::new(5);
+            assert_eq!(u32::MAX, bit.query(..).0);
+            bit.add(3, 7u32);
+            bit.add(1, 9u32);
+            assert_eq!(9, bit.query(..=2).0);
+            assert_eq!(7, bit.query(..4).0);
+        }
+
+        #[test]
+        #[should_panic(expected = "MonoidBIT only supports prefix queries")]
+        fn test_non_prefix() {
+            MonoidBIT::<Max<i64>>::new(5).query(1..3);
+        }
+
+        #[test]
+        fn test_prefix() {
+            let mut bit = MonoidBIT::<Max<i64>>::new(4);
+            bit.add(2, 5);
+            assert_eq!(i64::MIN, bit.prefix(0).0);
+            assert_eq!(i64::MIN, bit.prefix(2).0);
+            assert_eq!(5, bit.prefix(3).0);
+            for k in 0..=4 {
+                assert_eq!(bit.query(..k).0, bit.prefix(k).0);
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "range bound overflows usize")]
+        fn test_query_end_overflow() {
+            MonoidBIT::<Max<i64>>::new(5).query(..=usize::MAX);
+        }
     }
 }
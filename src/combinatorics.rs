@@ -0,0 +1,114 @@
+use crate::math::mod_inv;
+
+pub struct Combinatorics {
+    modulus: u64,
+    fact: Vec<u64>,
+    inv_fact: Vec<u64>,
+}
+
+impl Combinatorics {
+    /// `modulus` must be a prime greater than `max_n`.
+    pub fn new(max_n: usize, modulus: u64) -> Self {
+        let mut fact = vec![1 % modulus; max_n + 1];
+        for i in 1..=max_n {
+            fact[i] = mul(fact[i - 1], i as u64, modulus);
+        }
+        let mut inv_fact = vec![0; max_n + 1];
+        inv_fact[max_n] = mod_inv(fact[max_n], modulus).unwrap();
+        for i in (1..=max_n).rev() {
+            inv_fact[i - 1] = mul(inv_fact[i], i as u64, modulus);
+        }
+        Self {
+            modulus,
+            fact,
+            inv_fact,
+        }
+    }
+
+    pub fn factorial(&self, n: usize) -> u64 {
+        self.fact[n]
+    }
+
+    pub fn comb(&self, n: usize, r: usize) -> u64 {
+        if r > n {
+            return 0;
+        }
+        let m = self.modulus;
+        mul(
+            mul(self.fact[n], self.inv_fact[r], m),
+            self.inv_fact[n - r],
+            m,
+        )
+    }
+
+    pub fn perm(&self, n: usize, r: usize) -> u64 {
+        if r > n {
+            return 0;
+        }
+        mul(self.fact[n], self.inv_fact[n - r], self.modulus)
+    }
+}
+
+fn mul(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+#[cfg(test)]
+mod test {
+    use super::Combinatorics;
+
+    const MOD: u64 = 1_000_000_007;
+
+    #[test]
+    fn test_factorial() {
+        let c = Combinatorics::new(20, MOD);
+        assert_eq!(1, c.factorial(0));
+        assert_eq!(120, c.factorial(5));
+        assert_eq!(2_432_902_008_176_640_000 % MOD, c.factorial(20));
+    }
+
+    #[test]
+    fn test_comb() {
+        let c = Combinatorics::new(1000, MOD);
+        assert_eq!(10, c.comb(5, 2));
+        assert_eq!(0, c.comb(3, 4));
+        for n in 0..=1000 {
+            assert_eq!(1, c.comb(n, 0));
+            assert_eq!(1, c.comb(n, n));
+        }
+        for n in 1..=1000 {
+            for r in 1..n {
+                assert_eq!(
+                    (c.comb(n - 1, r - 1) + c.comb(n - 1, r)) % MOD,
+                    c.comb(n, r)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_comb_small_prime() {
+        let c = Combinatorics::new(6, 7);
+        assert_eq!(20 % 7, c.comb(6, 3));
+        assert_eq!(6, c.factorial(3));
+    }
+
+    #[test]
+    fn test_perm() {
+        let c = Combinatorics::new(10, MOD);
+        assert_eq!(60, c.perm(5, 3));
+        assert_eq!(1, c.perm(5, 0));
+        assert_eq!(120, c.perm(5, 5));
+        assert_eq!(0, c.perm(2, 3));
+    }
+
+    #[test]
+    fn test_large_modulus() {
+        const M61: u64 = (1 << 61) - 1;
+        let c = Combinatorics::new(60, M61);
+        assert_eq!(2_432_902_008_176_640_000 % M61, c.factorial(20));
+        assert_eq!(118_264_581_564_861_424, c.comb(60, 30));
+        assert_eq!(c.factorial(20), c.perm(20, 20));
+        assert_eq!(60 * 59 * 58, c.perm(60, 3));
+    }
+}
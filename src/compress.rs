@@ -0,0 +1,58 @@
+pub fn sorted_unique<T: Ord + Clone>(data: &[T]) -> Vec<T> {
+    let mut unique = data.to_vec();
+    unique.sort();
+    unique.dedup();
+    unique
+}
+
+/// Rank of `value` in `sorted`, the output of `sorted_unique`. For a value that is not
+/// present this is the number of smaller elements, i.e. the position it would be inserted at.
+pub fn index_of<T: Ord>(sorted: &[T], value: &T) -> usize {
+    sorted.partition_point(|x| x < value)
+}
+
+pub fn compress<T: Ord + Clone>(values: &[T]) -> (Vec<usize>, Vec<T>) {
+    let unique = sorted_unique(values);
+    let ranks = values.iter().map(|v| index_of(&unique, v)).collect();
+    (ranks, unique)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{compress, index_of, sorted_unique};
+
+    #[test]
+    fn test_compress() {
+        assert_eq!(
+            (vec![2, 0, 2, 1], vec![10, 30, 50]),
+            compress(&[50, 10, 50, 30])
+        );
+        assert_eq!((vec![], Vec::<i32>::new()), compress(&[]));
+        assert_eq!((vec![0, 0], vec![7]), compress(&[7, 7]));
+        assert_eq!(
+            (vec![1, 0], vec!["a".to_string(), "b".to_string()]),
+            compress(&["b".to_string(), "a".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_sorted_unique() {
+        assert_eq!(vec![10, 30, 50], sorted_unique(&[50, 10, 50, 30]));
+        assert_eq!(Vec::<i32>::new(), sorted_unique(&[]));
+        assert_eq!(vec!['a', 'b'], sorted_unique(&['b', 'a', 'b', 'a']));
+    }
+
+    #[test]
+    fn test_index_of() {
+        let unique = sorted_unique(&[50, 10, 50, 30, -5]);
+        assert_eq!(0, index_of(&unique, &-5));
+        assert_eq!(1, index_of(&unique, &10));
+        assert_eq!(2, index_of(&unique, &30));
+        assert_eq!(3, index_of(&unique, &50));
+
+        assert_eq!(0, index_of(&unique, &-100));
+        assert_eq!(2, index_of(&unique, &20));
+        assert_eq!(4, index_of(&unique, &100));
+        assert_eq!(0, index_of(&[], &1));
+    }
+}
@@ -0,0 +1,220 @@
+use crate::math::IntExt;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Reduced fraction with a positive denominator, so equal values compare and hash equal.
+/// Construction and arithmetic panic when the reduced result does not fit in `i64`,
+/// e.g. `Fraction::new(i64::MIN, -1)`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Fraction {
+    num: i64,
+    den: i64,
+}
+
+impl Fraction {
+    pub fn new(num: i64, den: i64) -> Self {
+        Self::from_wide(num as i128, den as i128)
+    }
+
+    fn from_wide(num: i128, den: i128) -> Self {
+        assert!(den != 0);
+        let g = num.gcd(den);
+        let sign = if den < 0 { -1 } else { 1 };
+        Self {
+            num: i64::try_from(num / g * sign).expect("Fraction overflow"),
+            den: i64::try_from(den / g * sign).expect("Fraction overflow"),
+        }
+    }
+
+    pub fn num(&self) -> i64 {
+        self.num
+    }
+
+    pub fn den(&self) -> i64 {
+        self.den
+    }
+}
+
+impl From<i64> for Fraction {
+    fn from(n: i64) -> Self {
+        Self { num: n, den: 1 }
+    }
+}
+
+impl Ord for Fraction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num as i128 * other.den as i128).cmp(&(other.num as i128 * self.den as i128))
+    }
+}
+
+impl PartialOrd for Fraction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Fraction {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::from_wide(
+            self.num as i128 * rhs.den as i128 + rhs.num as i128 * self.den as i128,
+            self.den as i128 * rhs.den as i128,
+        )
+    }
+}
+
+impl Sub for Fraction {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl Mul for Fraction {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::from_wide(
+            self.num as i128 * rhs.num as i128,
+            self.den as i128 * rhs.den as i128,
+        )
+    }
+}
+
+impl Div for Fraction {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Self::from_wide(
+            self.num as i128 * rhs.den as i128,
+            self.den as i128 * rhs.num as i128,
+        )
+    }
+}
+
+impl Neg for Fraction {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::from_wide(-(self.num as i128), self.den as i128)
+    }
+}
+
+impl Display for Fraction {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}/{}", self.num, self.den)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Fraction;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashMap;
+    use std::hash::{Hash, Hasher};
+
+    fn hash(f: &Fraction) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        f.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(Fraction::new(1, 2), Fraction::new(2, 4));
+        assert_eq!(hash(&Fraction::new(1, 2)), hash(&Fraction::new(2, 4)));
+        assert_eq!(Fraction::new(-1, 2), Fraction::new(3, -6));
+        assert_eq!(
+            (-1, 2),
+            (Fraction::new(3, -6).num(), Fraction::new(3, -6).den())
+        );
+        assert_eq!(Fraction::new(0, 1), Fraction::new(0, -5));
+        assert_eq!(Fraction::from(3), Fraction::new(6, 2));
+
+        let mut slopes = HashMap::new();
+        for &(dx, dy) in [(2, 4), (1, 2), (-3, -6), (1, 3)].iter() {
+            *slopes.entry(Fraction::new(dy, dx)).or_insert(0) += 1;
+        }
+        assert_eq!(2, slopes.len());
+        assert_eq!(3, slopes[&Fraction::new(2, 1)]);
+    }
+
+    #[test]
+    fn test_ord() {
+        let mut v = [
+            Fraction::new(1, 2),
+            Fraction::new(-1, 3),
+            Fraction::new(2, 3),
+            Fraction::new(1, -2),
+            Fraction::from(0),
+        ];
+        v.sort();
+        let values = v
+            .iter()
+            .map(|f| f.num() as f64 / f.den() as f64)
+            .collect::<Vec<_>>();
+        assert!(values.windows(2).all(|w| w[0] < w[1]));
+        assert!(Fraction::new(i64::MAX, 2) > Fraction::new(i64::MAX - 1, 2));
+    }
+
+    #[test]
+    fn test_ops() {
+        let a = Fraction::new(1, 2);
+        let b = Fraction::new(1, 3);
+        assert_eq!(Fraction::new(5, 6), a + b);
+        assert_eq!(Fraction::new(1, 6), a - b);
+        assert_eq!(Fraction::new(1, 6), a * b);
+        assert_eq!(Fraction::new(3, 2), a / b);
+        assert_eq!(Fraction::new(-1, 2), -a);
+        assert_eq!(Fraction::new(-2, 1), a / Fraction::new(-1, 4));
+        assert_eq!("-1/6", format!("{}", b - a));
+    }
+
+    #[test]
+    fn test_extreme_values() {
+        let min = Fraction::new(i64::MIN, 1);
+        assert_eq!((i64::MIN, 1), (min.num(), min.den()));
+        assert_eq!(Fraction::from(1), Fraction::new(i64::MIN, i64::MIN));
+        assert_eq!(Fraction::new(-2, 1), Fraction::new(i64::MIN, 1 << 62));
+        assert_eq!(Fraction::new(i64::MIN / 2, 1), Fraction::new(i64::MIN, 2));
+        assert_eq!(
+            Fraction::from(i64::MAX),
+            Fraction::new(i64::MAX, 2) * Fraction::from(2)
+        );
+        assert_eq!(Fraction::new(-i64::MAX, 1), -Fraction::from(i64::MAX));
+    }
+
+    #[test]
+    #[should_panic(expected = "Fraction overflow")]
+    fn test_overflow_mul() {
+        let _ = Fraction::from(i64::MAX) * Fraction::from(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Fraction overflow")]
+    fn test_overflow_add() {
+        let _ = Fraction::from(i64::MAX) + Fraction::from(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Fraction overflow")]
+    fn test_overflow_new() {
+        Fraction::new(i64::MIN, -1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Fraction overflow")]
+    fn test_overflow_neg() {
+        let _ = -Fraction::from(i64::MIN);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_denominator() {
+        Fraction::new(1, 0);
+    }
+}
@@ -0,0 +1,1078 @@
+use crate::binary_search::binary_search;
+use crate::vector::{Circle, LineSegment, Scalar, Turn, Vec2, Vector2D, CCW, EPS};
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ConvexHullOptions {
+    pub include_collinear: bool,
+    pub dedup: bool,
+}
+
+impl Default for ConvexHullOptions {
+    fn default() -> Self {
+        Self {
+            include_collinear: false,
+            dedup: true,
+        }
+    }
+}
+
+fn half_hull<'a, T: Vector2D + Copy + 'a>(
+    points: impl Iterator<Item = &'a T>,
+    include_collinear: bool,
+) -> Vec<T> {
+    let zero = T::Scalar::zero();
+    let mut hull: Vec<T> = vec![];
+    for &p in points {
+        while hull.len() >= 2 {
+            let a = hull[hull.len() - 2];
+            let b = hull[hull.len() - 1];
+            let det = b.sub(&a).cross(&p.sub(&a));
+            if det < zero || (det == zero && !include_collinear) {
+                hull.pop();
+            } else {
+                break;
+            }
+        }
+        hull.push(p);
+    }
+    hull
+}
+
+/// Works on any `Vector2D`; with integer coordinates (`IVec2`) every orientation test is
+/// exact, so collinear points are never misclassified.
+pub fn convex_hull_with<T: Vector2D + Copy + PartialEq>(
+    points: &[T],
+    options: ConvexHullOptions,
+) -> Vec<T> {
+    let cmp = |a: &T, b: &T| (a.x(), a.y()).partial_cmp(&(b.x(), b.y())).unwrap();
+    let mut points = points.to_vec();
+    points.sort_by(cmp);
+    let mut counts: Vec<(T, usize)> = vec![];
+    for p in points {
+        match counts.last_mut() {
+            Some((last, count)) if *last == p => *count += 1,
+            _ => counts.push((p, 1)),
+        }
+    }
+    let points = counts.iter().map(|&(p, _)| p).collect::<Vec<_>>();
+    let hull = unique_convex_hull(&points, options.include_collinear);
+    if options.dedup {
+        return hull;
+    }
+
+    let mut ret = vec![];
+    for p in hull {
+        let index = counts.binary_search_by(|(q, _)| cmp(q, &p)).unwrap();
+        let count = counts[index].1;
+        ret.extend(std::iter::repeat_n(p, count));
+    }
+    ret
+}
+
+pub fn convex_hull<T: Vector2D + Copy + PartialEq>(points: &[T]) -> Vec<T> {
+    convex_hull_with(points, ConvexHullOptions::default())
+}
+
+fn unique_convex_hull<T: Vector2D + Copy>(points: &[T], include_collinear: bool) -> Vec<T> {
+    let (first, last) = match (points.first(), points.last()) {
+        (Some(&first), Some(&last)) => (first, last),
+        _ => return vec![],
+    };
+
+    let direction = last.sub(&first);
+    if points
+        .iter()
+        .all(|p| direction.cross(&p.sub(&first)) == T::Scalar::zero())
+    {
+        if include_collinear || points.len() == 1 {
+            return points.to_vec();
+        }
+        return vec![first, last];
+    }
+
+    let mut lower = half_hull(points.iter(), include_collinear);
+    let mut upper = half_hull(points.iter().rev(), include_collinear);
+    lower.pop();
+    upper.pop();
+    lower.append(&mut upper);
+    lower
+}
+
+fn shoelace(points: &[Vec2]) -> f64 {
+    (0..points.len())
+        .map(|i| points[i].cross(&points[(i + 1) % points.len()]))
+        .sum::<f64>()
+}
+
+pub fn polygon_area(points: &[Vec2]) -> f64 {
+    shoelace(points).abs() / 2.0
+}
+
+/// `CounterClockwise` or `Clockwise` by the sign of the signed area; `None` when
+/// the area vanishes (fewer than three points or all collinear).
+pub fn polygon_orientation(points: &[Vec2]) -> Option<CCW> {
+    let area = shoelace(points);
+    if area > EPS {
+        Some(CCW::CounterClockwise)
+    } else if area < -EPS {
+        Some(CCW::Clockwise)
+    } else {
+        None
+    }
+}
+
+/// Whether a simple polygon is convex in either orientation; collinear vertices are
+/// allowed, but a polygon without any turn is degenerate and not convex.
+pub fn is_convex(points: &[Vec2]) -> bool {
+    let n = points.len();
+    let mut turns = (0..n)
+        .map(|i| Turn::of(&points[i], &points[(i + 1) % n], &points[(i + 2) % n]))
+        .filter(|&turn| turn != Turn::Straight);
+    match turns.next() {
+        Some(first) => turns.all(|turn| turn == first),
+        None => false,
+    }
+}
+
+pub fn polygon_perimeter(points: &[Vec2]) -> f64 {
+    (0..points.len())
+        .map(|i| points[i].distance(&points[(i + 1) % points.len()]))
+        .sum()
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum PointLocation {
+    Inside,
+    Outside,
+    Boundary,
+}
+
+pub fn point_in_polygon(poly: &[Vec2], p: &Vec2) -> PointLocation {
+    let mut inside = false;
+    for i in 0..poly.len() {
+        let (a, b) = (poly[i], poly[(i + 1) % poly.len()]);
+        if LineSegment::new(a, b).contains_eps(p, EPS) {
+            return PointLocation::Boundary;
+        }
+        let (a, b) = (a - *p, b - *p);
+        let (a, b) = if a.1 > b.1 { (b, a) } else { (a, b) };
+        if a.1 <= 0.0 && 0.0 < b.1 && a.cross(&b) > 0.0 {
+            inside = !inside;
+        }
+    }
+    if inside {
+        PointLocation::Inside
+    } else {
+        PointLocation::Outside
+    }
+}
+
+/// `poly` must be strictly convex and in counter-clockwise order.
+pub fn point_in_convex(poly: &[Vec2], p: &Vec2) -> PointLocation {
+    let n = poly.len();
+    assert!(n >= 3);
+    let v0 = &poly[0];
+    let (first, last) = (side(v0, &poly[1], p), side(v0, &poly[n - 1], p));
+    if first < 0 || last > 0 {
+        return PointLocation::Outside;
+    }
+    let i = binary_search(1i64, n as i64 - 1, |i| side(v0, &poly[i as usize], p) >= 0) as usize;
+    match side(&poly[i], &poly[i + 1], p) {
+        -1 => PointLocation::Outside,
+        0 => PointLocation::Boundary,
+        _ if (i == 1 && first == 0) || (i == n - 2 && last == 0) => PointLocation::Boundary,
+        _ => PointLocation::Inside,
+    }
+}
+
+pub fn clip_polygon(poly: &[Vec2], a: &Vec2, b: &Vec2) -> Vec<Vec2> {
+    let ab = *b - *a;
+    let mut clipped = vec![];
+    for i in 0..poly.len() {
+        let (p, q) = (poly[i], poly[(i + 1) % poly.len()]);
+        let (sp, sq) = (side(a, b, &p), side(a, b, &q));
+        if sp >= 0 {
+            clipped.push(p);
+        }
+        if sp * sq < 0 {
+            let (cp, cq) = (ab.cross(&(p - *a)), ab.cross(&(q - *a)));
+            clipped.push(p + (q - p) * (cp / (cp - cq)));
+        }
+    }
+    clipped
+}
+
+pub fn farthest_point(points: &[Vec2], from: &Vec2) -> Option<Vec2> {
+    points
+        .iter()
+        .max_by(|a, b| a.distance(from).partial_cmp(&b.distance(from)).unwrap())
+        .copied()
+}
+
+pub fn segment_distance(a: &LineSegment<Vec2>, b: &LineSegment<Vec2>) -> f64 {
+    if a.intersects_inclusive(b) {
+        return 0.0;
+    }
+    let ((a0, a1), (b0, b1)) = (a.endpoints(), b.endpoints());
+    a.distance_to_point(b0)
+        .min(a.distance_to_point(b1))
+        .min(b.distance_to_point(a0))
+        .min(b.distance_to_point(a1))
+}
+
+pub fn point_line_distance<T: Vector2D>(a: &T, b: &T, p: &T) -> f64 {
+    let ab = b.sub(a);
+    (ab.cross(&p.sub(a)).to_f64() / ab.length()).abs()
+}
+
+pub fn side<T: Vector2D>(a: &T, b: &T, p: &T) -> i8 {
+    b.sub(a).cross_sign(&p.sub(a)) as i8
+}
+
+pub fn project<T: Vector2D<Scalar = f64>>(p: &T, a: &T, b: &T) -> T {
+    let ab = b.sub(a);
+    let t = p.sub(a).dot(&ab) / ab.dot(&ab);
+    T::new(a.x() + ab.x() * t, a.y() + ab.y() * t)
+}
+
+pub fn reflect<T: Vector2D<Scalar = f64>>(p: &T, a: &T, b: &T) -> T {
+    let q = project(p, a, b);
+    T::new(q.x() * 2.0 - p.x(), q.y() * 2.0 - p.y())
+}
+
+pub fn triangle_area(a: &Vec2, b: &Vec2, c: &Vec2) -> f64 {
+    (*b - *a).cross(&(*c - *a)).abs() / 2.0
+}
+
+/// `None` when the three points are collinear.
+pub fn circumcircle(a: &Vec2, b: &Vec2, c: &Vec2) -> Option<Circle> {
+    let ab = *b - *a;
+    let ac = *c - *a;
+    let det = 2.0 * ab.cross(&ac);
+    if det.abs() < EPS {
+        return None;
+    }
+    let (ab2, ac2) = (ab.dot(&ab), ac.dot(&ac));
+    let center = *a + Vec2(ac.1 * ab2 - ab.1 * ac2, ab.0 * ac2 - ac.0 * ab2) / det;
+    Some(Circle::new(center, center.distance(a)))
+}
+
+fn diameter_circle(a: Vec2, b: Vec2) -> Circle {
+    Circle::new((a + b) / 2.0, a.distance(&b) / 2.0)
+}
+
+pub fn min_enclosing_circle(points: &[Vec2]) -> Circle {
+    let mut points = points.to_vec();
+    points.shuffle(&mut SmallRng::seed_from_u64(0x5eed));
+    let inside = |c: &Circle, p: &Vec2| c.center.distance(p) <= c.radius + EPS;
+
+    let mut circle = Circle::new(points.first().copied().unwrap_or(Vec2(0.0, 0.0)), 0.0);
+    for i in 1..points.len() {
+        if inside(&circle, &points[i]) {
+            continue;
+        }
+        circle = Circle::new(points[i], 0.0);
+        for j in 0..i {
+            if inside(&circle, &points[j]) {
+                continue;
+            }
+            circle = diameter_circle(points[i], points[j]);
+            for k in 0..j {
+                if inside(&circle, &points[k]) {
+                    continue;
+                }
+                circle = circumcircle(&points[i], &points[j], &points[k]).unwrap_or_else(|| {
+                    let candidates = [(i, j), (i, k), (j, k)];
+                    let &(u, v) = candidates
+                        .iter()
+                        .max_by(|a, b| {
+                            let da = points[a.0].distance(&points[a.1]);
+                            let db = points[b.0].distance(&points[b.1]);
+                            da.partial_cmp(&db).unwrap()
+                        })
+                        .unwrap();
+                    diameter_circle(points[u], points[v])
+                });
+            }
+        }
+    }
+    circle
+}
+
+struct CoverTree {
+    ys: Vec<i64>,
+    count: Vec<usize>,
+    covered: Vec<i64>,
+}
+
+impl CoverTree {
+    fn new(ys: Vec<i64>) -> Self {
+        let size = 4 * ys.len().max(1);
+        Self {
+            ys,
+            count: vec![0; size],
+            covered: vec![0; size],
+        }
+    }
+
+    fn covered(&self) -> i64 {
+        self.covered[1]
+    }
+
+    fn add(&mut self, begin: usize, end: usize, value: isize) {
+        if self.ys.len() >= 2 {
+            self.update(1, 0, self.ys.len() - 1, begin, end, value);
+        }
+    }
+
+    fn update(&mut self, node: usize, l: usize, r: usize, begin: usize, end: usize, value: isize) {
+        if end <= l || r <= begin {
+            return;
+        }
+        if begin <= l && r <= end {
+            self.count[node] = (self.count[node] as isize + value) as usize;
+        } else {
+            let m = (l + r) / 2;
+            self.update(node * 2, l, m, begin, end, value);
+            self.update(node * 2 + 1, m, r, begin, end, value);
+        }
+        self.covered[node] = if self.count[node] > 0 {
+            self.ys[r] - self.ys[l]
+        } else if r - l == 1 {
+            0
+        } else {
+            self.covered[node * 2] + self.covered[node * 2 + 1]
+        };
+    }
+}
+
+pub fn rectangle_union_area(rects: &[(i64, i64, i64, i64)]) -> i64 {
+    let mut ys = rects
+        .iter()
+        .flat_map(|&(_, y1, _, y2)| vec![y1, y2])
+        .collect::<Vec<_>>();
+    ys.sort_unstable();
+    ys.dedup();
+    let index = |y: i64| ys.binary_search(&y).unwrap();
+
+    let mut events = rects
+        .iter()
+        .filter(|&&(x1, y1, x2, y2)| x1 < x2 && y1 < y2)
+        .flat_map(|&(x1, y1, x2, y2)| {
+            vec![
+                (x1, 1, index(y1), index(y2)),
+                (x2, -1, index(y1), index(y2)),
+            ]
+        })
+        .collect::<Vec<_>>();
+    events.sort_unstable();
+
+    let mut tree = CoverTree::new(ys.clone());
+    let mut area = 0;
+    let mut prev_x = events.first().map_or(0, |e| e.0);
+    for (x, value, begin, end) in events {
+        area += tree.covered() * (x - prev_x);
+        tree.add(begin, end, value);
+        prev_x = x;
+    }
+    area
+}
+
+#[cfg(test)]
+mod test {
+    mod convex_hull {
+        use super::super::{convex_hull, convex_hull_with, ConvexHullOptions};
+        use crate::vector::{IVec2, Vec2};
+        use rand::rngs::SmallRng;
+        use rand::{Rng, SeedableRng};
+
+        fn points() -> Vec<Vec2> {
+            vec![
+                Vec2(2.0, 2.0),
+                Vec2(1.0, 0.0),
+                Vec2(0.0, 0.0),
+                Vec2(1.0, 1.0),
+                Vec2(2.0, 0.0),
+                Vec2(0.0, 2.0),
+                Vec2(1.0, 0.0),
+                Vec2(2.0, 2.0),
+                Vec2(0.0, 0.0),
+            ]
+        }
+
+        #[test]
+        fn test_exclude_collinear_dedup() {
+            let options = ConvexHullOptions {
+                include_collinear: false,
+                dedup: true,
+            };
+            assert_eq!(
+                vec![
+                    Vec2(0.0, 0.0),
+                    Vec2(2.0, 0.0),
+                    Vec2(2.0, 2.0),
+                    Vec2(0.0, 2.0)
+                ],
+                convex_hull_with(&points(), options)
+            );
+        }
+
+        #[test]
+        fn test_exclude_collinear_no_dedup() {
+            let options = ConvexHullOptions {
+                include_collinear: false,
+                dedup: false,
+            };
+            assert_eq!(
+                vec![
+                    Vec2(0.0, 0.0),
+                    Vec2(0.0, 0.0),
+                    Vec2(2.0, 0.0),
+                    Vec2(2.0, 2.0),
+                    Vec2(2.0, 2.0),
+                    Vec2(0.0, 2.0)
+                ],
+                convex_hull_with(&points(), options)
+            );
+        }
+
+        #[test]
+        fn test_include_collinear_dedup() {
+            let options = ConvexHullOptions {
+                include_collinear: true,
+                dedup: true,
+            };
+            assert_eq!(
+                vec![
+                    Vec2(0.0, 0.0),
+                    Vec2(1.0, 0.0),
+                    Vec2(2.0, 0.0),
+                    Vec2(2.0, 2.0),
+                    Vec2(0.0, 2.0)
+                ],
+                convex_hull_with(&points(), options)
+            );
+        }
+
+        #[test]
+        fn test_include_collinear_no_dedup() {
+            let options = ConvexHullOptions {
+                include_collinear: true,
+                dedup: false,
+            };
+            assert_eq!(
+                vec![
+                    Vec2(0.0, 0.0),
+                    Vec2(0.0, 0.0),
+                    Vec2(1.0, 0.0),
+                    Vec2(1.0, 0.0),
+                    Vec2(2.0, 0.0),
+                    Vec2(2.0, 2.0),
+                    Vec2(2.0, 2.0),
+                    Vec2(0.0, 2.0)
+                ],
+                convex_hull_with(&points(), options)
+            );
+        }
+
+        #[test]
+        fn test_collinear_points() {
+            let points = vec![Vec2(2.0, 2.0), Vec2(0.0, 0.0), Vec2(1.0, 1.0)];
+            let options = ConvexHullOptions {
+                include_collinear: true,
+                dedup: true,
+            };
+            assert_eq!(
+                vec![Vec2(0.0, 0.0), Vec2(1.0, 1.0), Vec2(2.0, 2.0)],
+                convex_hull_with(&points, options)
+            );
+            assert_eq!(
+                vec![Vec2(0.0, 0.0), Vec2(2.0, 2.0)],
+                convex_hull_with(&points, ConvexHullOptions::default())
+            );
+        }
+
+        #[test]
+        fn test_convex_hull() {
+            let mut points = (0..=4)
+                .flat_map(|x| (0..=4).map(move |y| Vec2(x as f64, y as f64)))
+                .collect::<Vec<_>>();
+            points.reverse();
+            assert_eq!(
+                vec![
+                    Vec2(0.0, 0.0),
+                    Vec2(4.0, 0.0),
+                    Vec2(4.0, 4.0),
+                    Vec2(0.0, 4.0)
+                ],
+                convex_hull(&points)
+            );
+
+            let points = vec![
+                Vec2(0.0, 3.0),
+                Vec2(1.0, 1.0),
+                Vec2(2.0, 2.0),
+                Vec2(4.0, 4.0),
+                Vec2(0.0, 0.0),
+                Vec2(1.0, 2.0),
+                Vec2(3.0, 1.0),
+                Vec2(3.0, 3.0),
+            ];
+            assert_eq!(
+                vec![
+                    Vec2(0.0, 0.0),
+                    Vec2(3.0, 1.0),
+                    Vec2(4.0, 4.0),
+                    Vec2(0.0, 3.0)
+                ],
+                convex_hull(&points)
+            );
+        }
+
+        #[test]
+        fn test_convex_hull_collinear() {
+            let points = vec![
+                Vec2(3.0, 0.0),
+                Vec2(1.0, 0.0),
+                Vec2(0.0, 0.0),
+                Vec2(2.0, 0.0),
+            ];
+            assert_eq!(vec![Vec2(0.0, 0.0), Vec2(3.0, 0.0)], convex_hull(&points));
+        }
+
+        #[test]
+        fn test_convex_hull_few_points() {
+            assert_eq!(Vec::<Vec2>::new(), convex_hull(&[]));
+            assert_eq!(vec![Vec2(1.0, 2.0)], convex_hull(&[Vec2(1.0, 2.0)]));
+            assert_eq!(
+                vec![Vec2(1.0, 2.0)],
+                convex_hull(&[Vec2(1.0, 2.0), Vec2(1.0, 2.0)])
+            );
+            assert_eq!(
+                vec![Vec2(-1.0, 5.0), Vec2(1.0, 2.0)],
+                convex_hull(&[Vec2(1.0, 2.0), Vec2(-1.0, 5.0)])
+            );
+        }
+
+        #[test]
+        fn test_convex_hull_integer_matches_float() {
+            let mut rng = SmallRng::seed_from_u64(889);
+            for _ in 0..100 {
+                let n = rng.gen_range(1, 20);
+                let points = (0..n)
+                    .map(|_| IVec2(rng.gen_range(-5, 6), rng.gen_range(-5, 6)))
+                    .collect::<Vec<_>>();
+                let floats = points
+                    .iter()
+                    .map(|p| Vec2(p.0 as f64, p.1 as f64))
+                    .collect::<Vec<_>>();
+                for &include_collinear in &[false, true] {
+                    let options = ConvexHullOptions {
+                        include_collinear,
+                        dedup: true,
+                    };
+                    let expected = convex_hull_with(&floats, options);
+                    let actual = convex_hull_with(&points, options)
+                        .into_iter()
+                        .map(|p| Vec2(p.0 as f64, p.1 as f64))
+                        .collect::<Vec<_>>();
+                    assert_eq!(expected, actual);
+                }
+            }
+        }
+
+        #[test]
+        fn test_convex_hull_integer_collinear_exact() {
+            let (a, d) = (
+                IVec2(-1_000_000_007, 999_999_937),
+                IVec2(123_456_791, -98_765_431),
+            );
+            let on_edge = |k: i64| IVec2(a.0 + d.0 * k, a.1 + d.1 * k);
+            let apex = IVec2(a.0 + 1, a.1 + 1);
+            let mut points = (0..=16).map(on_edge).collect::<Vec<_>>();
+            points.push(apex);
+
+            let hull = convex_hull(&points);
+            assert_eq!(3, hull.len());
+            assert!([on_edge(0), on_edge(16), apex]
+                .iter()
+                .all(|p| hull.contains(p)));
+
+            let options = ConvexHullOptions {
+                include_collinear: true,
+                dedup: true,
+            };
+            assert_eq!(18, convex_hull_with(&points, options).len());
+
+            points.pop();
+            assert_eq!(vec![on_edge(0), on_edge(16)], convex_hull(&points));
+        }
+    }
+
+    mod rectangle_union_area {
+        use super::super::rectangle_union_area;
+
+        #[test]
+        fn test_empty() {
+            assert_eq!(0, rectangle_union_area(&[]));
+            assert_eq!(0, rectangle_union_area(&[(0, 0, 0, 5), (1, 1, 3, 1)]));
+        }
+
+        #[test]
+        fn test_overlapping() {
+            let rects = [(0, 0, 3, 3), (1, 1, 4, 4)];
+            assert_eq!(14, rectangle_union_area(&rects));
+            assert_eq!(9, rectangle_union_area(&[(0, 0, 3, 3), (1, 1, 2, 2)]));
+            assert_eq!(
+                6,
+                rectangle_union_area(&[(0, 0, 3, 1), (1, -1, 2, 3), (0, 0, 3, 1)])
+            );
+        }
+
+        #[test]
+        fn test_disjoint() {
+            let rects = [(0, 0, 2, 2), (5, 5, 8, 6), (-3, 1, -1, 4)];
+            assert_eq!(4 + 3 + 6, rectangle_union_area(&rects));
+            assert_eq!(8, rectangle_union_area(&[(0, 0, 2, 2), (2, 0, 4, 2)]));
+        }
+    }
+
+    mod polygon {
+        use super::super::{
+            clip_polygon, convex_hull, is_convex, point_in_convex, point_in_polygon, polygon_area,
+            polygon_orientation, polygon_perimeter, PointLocation,
+        };
+        use crate::vector::{Vec2, CCW};
+        use rand::rngs::SmallRng;
+        use rand::{Rng, SeedableRng};
+
+        #[test]
+        fn test_polygon_area() {
+            let square = vec![
+                Vec2(0.0, 0.0),
+                Vec2(1.0, 0.0),
+                Vec2(1.0, 1.0),
+                Vec2(0.0, 1.0),
+            ];
+            assert_eq!(1.0, polygon_area(&square));
+            let triangle = vec![Vec2(0.0, 0.0), Vec2(4.0, 0.0), Vec2(0.0, 3.0)];
+            assert_eq!(6.0, polygon_area(&triangle));
+            let clockwise = triangle.into_iter().rev().collect::<Vec<_>>();
+            assert_eq!(6.0, polygon_area(&clockwise));
+            assert_eq!(0.0, polygon_area(&[]));
+        }
+
+        #[test]
+        fn test_polygon_perimeter() {
+            let square = vec![
+                Vec2(0.0, 0.0),
+                Vec2(1.0, 0.0),
+                Vec2(1.0, 1.0),
+                Vec2(0.0, 1.0),
+            ];
+            assert_eq!(4.0, polygon_perimeter(&square));
+            let triangle = vec![Vec2(0.0, 0.0), Vec2(4.0, 0.0), Vec2(0.0, 3.0)];
+            assert_eq!(12.0, polygon_perimeter(&triangle));
+            let clockwise = triangle.into_iter().rev().collect::<Vec<_>>();
+            assert_eq!(12.0, polygon_perimeter(&clockwise));
+        }
+
+        #[test]
+        fn test_point_in_polygon() {
+            let poly = vec![
+                Vec2(0.0, 0.0),
+                Vec2(4.0, 0.0),
+                Vec2(4.0, 4.0),
+                Vec2(2.0, 1.0),
+                Vec2(0.0, 4.0),
+            ];
+            assert_eq!(
+                PointLocation::Inside,
+                point_in_polygon(&poly, &Vec2(1.0, 1.0))
+            );
+            assert_eq!(
+                PointLocation::Inside,
+                point_in_polygon(&poly, &Vec2(3.5, 3.0))
+            );
+            assert_eq!(
+                PointLocation::Outside,
+                point_in_polygon(&poly, &Vec2(2.0, 3.0))
+            );
+            assert_eq!(
+                PointLocation::Outside,
+                point_in_polygon(&poly, &Vec2(5.0, 0.0))
+            );
+            assert_eq!(
+                PointLocation::Boundary,
+                point_in_polygon(&poly, &Vec2(2.0, 0.0))
+            );
+            assert_eq!(
+                PointLocation::Boundary,
+                point_in_polygon(&poly, &Vec2(3.0, 2.5))
+            );
+            assert_eq!(
+                PointLocation::Boundary,
+                point_in_polygon(&poly, &Vec2(0.0, 4.0))
+            );
+        }
+
+        #[test]
+        fn test_point_in_convex() {
+            let square = vec![
+                Vec2(0.0, 0.0),
+                Vec2(2.0, 0.0),
+                Vec2(2.0, 2.0),
+                Vec2(0.0, 2.0),
+            ];
+            assert_eq!(
+                PointLocation::Inside,
+                point_in_convex(&square, &Vec2(1.0, 1.0))
+            );
+            assert_eq!(
+                PointLocation::Boundary,
+                point_in_convex(&square, &Vec2(0.0, 0.0))
+            );
+            assert_eq!(
+                PointLocation::Boundary,
+                point_in_convex(&square, &Vec2(1.0, 0.0))
+            );
+            assert_eq!(
+                PointLocation::Boundary,
+                point_in_convex(&square, &Vec2(0.0, 1.0))
+            );
+            assert_eq!(
+                PointLocation::Boundary,
+                point_in_convex(&square, &Vec2(1.0, 2.0))
+            );
+            assert_eq!(
+                PointLocation::Outside,
+                point_in_convex(&square, &Vec2(3.0, 0.0))
+            );
+            assert_eq!(
+                PointLocation::Outside,
+                point_in_convex(&square, &Vec2(-1.0, 0.0))
+            );
+            assert_eq!(
+                PointLocation::Outside,
+                point_in_convex(&square, &Vec2(1.0, 3.0))
+            );
+        }
+
+        #[test]
+        fn test_point_in_convex_random() {
+            let mut rng = SmallRng::seed_from_u64(851);
+            for _ in 0..50 {
+                let points = (0..20)
+                    .map(|_| Vec2(rng.gen_range(-20, 21) as f64, rng.gen_range(-20, 21) as f64))
+                    .collect::<Vec<_>>();
+                let hull = convex_hull(&points);
+                if hull.len() < 3 {
+                    continue;
+                }
+                for _ in 0..200 {
+                    let p = Vec2(rng.gen_range(-25, 26) as f64, rng.gen_range(-25, 26) as f64);
+                    assert_eq!(point_in_polygon(&hull, &p), point_in_convex(&hull, &p));
+                }
+            }
+        }
+
+        #[test]
+        fn test_clip_polygon() {
+            let square = vec![
+                Vec2(0.0, 0.0),
+                Vec2(2.0, 0.0),
+                Vec2(2.0, 2.0),
+                Vec2(0.0, 2.0),
+            ];
+            assert_eq!(
+                vec![Vec2(0.0, 0.0), Vec2(2.0, 2.0), Vec2(0.0, 2.0)],
+                clip_polygon(&square, &Vec2(0.0, 0.0), &Vec2(1.0, 1.0))
+            );
+            assert_eq!(
+                vec![
+                    Vec2(0.0, 0.0),
+                    Vec2(2.0, 0.0),
+                    Vec2(2.0, 1.0),
+                    Vec2(1.0, 2.0),
+                    Vec2(0.0, 2.0),
+                ],
+                clip_polygon(&square, &Vec2(3.0, 0.0), &Vec2(0.0, 3.0))
+            );
+            let pentagon = clip_polygon(&square, &Vec2(3.0, 0.0), &Vec2(0.0, 3.0));
+            assert_eq!(3.5, polygon_area(&pentagon));
+
+            assert_eq!(
+                square,
+                clip_polygon(&square, &Vec2(5.0, 0.0), &Vec2(5.0, 1.0))
+            );
+            assert!(clip_polygon(&square, &Vec2(5.0, 1.0), &Vec2(5.0, 0.0)).is_empty());
+        }
+
+        #[test]
+        fn test_polygon_orientation() {
+            let mut square = vec![
+                Vec2(0.0, 0.0),
+                Vec2(1.0, 0.0),
+                Vec2(1.0, 1.0),
+                Vec2(0.0, 1.0),
+            ];
+            assert_eq!(Some(CCW::CounterClockwise), polygon_orientation(&square));
+            square.reverse();
+            assert_eq!(Some(CCW::Clockwise), polygon_orientation(&square));
+
+            let hull = convex_hull(&[
+                Vec2(3.0, 1.0),
+                Vec2(-2.0, 4.0),
+                Vec2(0.0, -1.0),
+                Vec2(0.5, 0.5),
+            ]);
+            assert_eq!(Some(CCW::CounterClockwise), polygon_orientation(&hull));
+
+            let line = [Vec2(0.0, 0.0), Vec2(1.0, 1.0), Vec2(3.0, 3.0)];
+            assert_eq!(None, polygon_orientation(&line));
+            assert_eq!(None, polygon_orientation(&[Vec2(1.0, 2.0)]));
+            assert_eq!(None, polygon_orientation(&[]));
+        }
+
+        #[test]
+        fn test_is_convex() {
+            let mut square = vec![
+                Vec2(0.0, 0.0),
+                Vec2(2.0, 0.0),
+                Vec2(2.0, 2.0),
+                Vec2(0.0, 2.0),
+            ];
+            assert!(is_convex(&square));
+            square.reverse();
+            assert!(is_convex(&square));
+
+            let with_collinear = [
+                Vec2(0.0, 0.0),
+                Vec2(1.0, 0.0),
+                Vec2(2.0, 0.0),
+                Vec2(2.0, 2.0),
+                Vec2(0.0, 2.0),
+            ];
+            assert!(is_convex(&with_collinear));
+
+            let concave = [
+                Vec2(0.0, 0.0),
+                Vec2(4.0, 0.0),
+                Vec2(4.0, 4.0),
+                Vec2(2.0, 1.0),
+                Vec2(0.0, 4.0),
+            ];
+            assert!(!is_convex(&concave));
+
+            assert!(!is_convex(&[
+                Vec2(0.0, 0.0),
+                Vec2(1.0, 1.0),
+                Vec2(2.0, 2.0)
+            ]));
+            assert!(!is_convex(&[]));
+        }
+    }
+
+    mod distance {
+        use super::super::{
+            farthest_point, point_line_distance, project, reflect, segment_distance, side,
+        };
+        use crate::vector::{IVec2, LineSegment, Vec2, Vector2D};
+
+        #[test]
+        fn test_point_line_distance() {
+            let a = Vec2(0.0, 0.0);
+            let b = Vec2(4.0, 0.0);
+            assert_eq!(3.0, point_line_distance(&a, &b, &Vec2(2.0, 3.0)));
+            assert_eq!(4.0, point_line_distance(&a, &b, &Vec2(7.0, -4.0)));
+            assert_eq!(0.0, point_line_distance(&a, &b, &Vec2(-5.0, 0.0)));
+            let d = point_line_distance(&a, &Vec2(1.0, 1.0), &Vec2(0.0, 2.0));
+            assert!((d - 2.0f64.sqrt()).abs() < 1e-10);
+        }
+
+        #[test]
+        fn test_project() {
+            let a = Vec2(0.0, 0.0);
+            let b = Vec2(2.0, 2.0);
+            assert_eq!(Vec2(1.0, 1.0), project(&Vec2(2.0, 0.0), &a, &b));
+            assert_eq!(Vec2(-1.0, -1.0), project(&Vec2(0.0, -2.0), &a, &b));
+            assert_eq!(Vec2(3.0, 3.0), project(&Vec2(3.0, 3.0), &a, &b));
+            assert_eq!(
+                Vec2(1.0, 0.0),
+                project(&Vec2(1.0, 5.0), &Vec2(-1.0, 0.0), &Vec2(4.0, 0.0))
+            );
+        }
+
+        #[test]
+        fn test_reflect() {
+            let a = Vec2(0.0, 0.0);
+            let b = Vec2(1.0, 0.0);
+            assert_eq!(Vec2(2.0, -3.0), reflect(&Vec2(2.0, 3.0), &a, &b));
+            assert_eq!(Vec2(-1.0, 4.0), reflect(&Vec2(-1.0, -4.0), &a, &b));
+            assert_eq!(Vec2(5.0, 0.0), reflect(&Vec2(5.0, 0.0), &a, &b));
+            assert_eq!(
+                Vec2(0.0, 2.0),
+                reflect(&Vec2(2.0, 0.0), &a, &Vec2(1.0, 1.0))
+            );
+        }
+
+        #[test]
+        fn test_side() {
+            let a = Vec2(0.0, 0.0);
+            let b = Vec2(2.0, 1.0);
+            assert_eq!(1, side(&a, &b, &Vec2(0.0, 1.0)));
+            assert_eq!(-1, side(&a, &b, &Vec2(1.0, 0.0)));
+            assert_eq!(0, side(&a, &b, &Vec2(4.0, 2.0)));
+            assert_eq!(0, side(&a, &b, &Vec2(-0.2, -0.1)));
+            assert_eq!(-1, side(&b, &a, &Vec2(0.0, 1.0)));
+            assert_eq!(1, side(&IVec2(0, 0), &IVec2(0, 5), &IVec2(-1, 7)));
+        }
+
+        #[test]
+        fn test_farthest_point() {
+            assert_eq!(None, farthest_point(&[], &Vec2(0.0, 0.0)));
+            let points = [
+                Vec2(1.0, 1.0),
+                Vec2(-3.0, 0.5),
+                Vec2(2.0, -2.5),
+                Vec2(0.0, 0.0),
+            ];
+            let from = Vec2(0.5, 0.0);
+            let brute = points.iter().map(|p| p.distance(&from)).fold(0.0, f64::max);
+            let farthest = farthest_point(&points, &from).unwrap();
+            assert_eq!(brute, farthest.distance(&from));
+            assert_eq!(Vec2(-3.0, 0.5), farthest);
+
+            let square = [
+                Vec2(1.0, 1.0),
+                Vec2(-1.0, 1.0),
+                Vec2(-1.0, -1.0),
+                Vec2(1.0, -1.0),
+            ];
+            let farthest = farthest_point(&square, &Vec2(0.0, 0.0)).unwrap();
+            assert!(square.contains(&farthest));
+            assert_eq!(2.0f64.sqrt(), farthest.distance(&Vec2(0.0, 0.0)));
+        }
+
+        #[test]
+        fn test_segment_distance() {
+            let s = |a: (f64, f64), b: (f64, f64)| LineSegment::new(Vec2::from(a), Vec2::from(b));
+            let base = s((0.0, 0.0), (4.0, 0.0));
+            assert_eq!(0.0, segment_distance(&base, &s((2.0, -1.0), (2.0, 1.0))));
+            assert_eq!(0.0, segment_distance(&base, &s((4.0, 0.0), (5.0, 5.0))));
+            assert_eq!(0.0, segment_distance(&base, &s((3.0, 0.0), (6.0, 0.0))));
+            assert_eq!(2.0, segment_distance(&base, &s((1.0, 2.0), (3.0, 2.0))));
+            assert_eq!(2.0, segment_distance(&base, &s((6.0, 0.0), (8.0, 0.0))));
+            assert_eq!(1.5, segment_distance(&base, &s((2.0, 1.5), (2.0, 3.0))));
+            assert_eq!(1.5, segment_distance(&s((2.0, 3.0), (2.0, 1.5)), &base));
+            assert_eq!(
+                2.0f64.sqrt(),
+                segment_distance(&base, &s((5.0, 1.0), (7.0, 3.0)))
+            );
+        }
+    }
+
+    mod min_enclosing_circle {
+        use super::super::{circumcircle, diameter_circle, min_enclosing_circle};
+        use crate::vector::{Circle, Vec2, Vector2D, EPS};
+        use rand::rngs::SmallRng;
+        use rand::{Rng, SeedableRng};
+
+        fn covers(circle: &Circle, points: &[Vec2]) -> bool {
+            points
+                .iter()
+                .all(|p| circle.center.distance(p) <= circle.radius + 1e-7)
+        }
+
+        fn brute_force(points: &[Vec2]) -> f64 {
+            let n = points.len();
+            let mut candidates = vec![];
+            for i in 0..n {
+                candidates.push(Circle::new(points[i], 0.0));
+                for j in i + 1..n {
+                    candidates.push(diameter_circle(points[i], points[j]));
+                    for k in j + 1..n {
+                        candidates.extend(circumcircle(&points[i], &points[j], &points[k]));
+                    }
+                }
+            }
+            candidates
+                .into_iter()
+                .filter(|c| covers(c, points))
+                .map(|c| c.radius)
+                .fold(f64::INFINITY, f64::min)
+        }
+
+        #[test]
+        fn test_small() {
+            assert_eq!(0.0, min_enclosing_circle(&[]).radius);
+            assert_eq!(
+                Circle::new(Vec2(1.0, 2.0), 0.0),
+                min_enclosing_circle(&[Vec2(1.0, 2.0)])
+            );
+            assert_eq!(
+                Circle::new(Vec2(1.0, 0.0), 1.0),
+                min_enclosing_circle(&[Vec2(0.0, 0.0), Vec2(2.0, 0.0)])
+            );
+
+            let c = min_enclosing_circle(&[Vec2(0.0, 0.0), Vec2(4.0, 0.0), Vec2(0.0, 3.0)]);
+            assert!(c.center.distance(&Vec2(2.0, 1.5)) < EPS);
+            assert!((c.radius - 2.5).abs() < EPS);
+
+            let c = min_enclosing_circle(&[Vec2(0.0, 0.0), Vec2(1.0, 1.0), Vec2(3.0, 3.0)]);
+            assert!(c.center.distance(&Vec2(1.5, 1.5)) < EPS);
+        }
+
+        #[test]
+        fn test_random() {
+            let mut rng = SmallRng::seed_from_u64(834);
+            for _ in 0..200 {
+                let n = rng.gen_range(1, 9);
+                let points = (0..n)
+                    .map(|_| Vec2(rng.gen_range(-10, 11) as f64, rng.gen_range(-10, 11) as f64))
+                    .collect::<Vec<_>>();
+                let c = min_enclosing_circle(&points);
+                assert!(covers(&c, &points));
+                assert!((c.radius - brute_force(&points)).abs() < 1e-7);
+            }
+        }
+    }
+
+    mod triangle {
+        use super::super::{circumcircle, triangle_area};
+        use crate::vector::{Vec2, Vector2D, EPS};
+
+        #[test]
+        fn test_triangle_area() {
+            let (a, b, c) = (Vec2(0.0, 0.0), Vec2(4.0, 0.0), Vec2(0.0, 3.0));
+            assert_eq!(6.0, triangle_area(&a, &b, &c));
+            assert_eq!(6.0, triangle_area(&a, &c, &b));
+            assert_eq!(0.0, triangle_area(&a, &b, &Vec2(8.0, 0.0)));
+        }
+
+        #[test]
+        fn test_circumcircle() {
+            let a = Vec2(1.0, 2.0);
+            let b = a + Vec2(2.0, 0.0);
+            let c = a + Vec2(1.0, 3.0f64.sqrt());
+            let circle = circumcircle(&a, &b, &c).unwrap();
+            assert!((circle.radius - 2.0 / 3.0f64.sqrt()).abs() < EPS);
+            assert!(circle
+                .center
+                .approx_eq(&(a + Vec2(1.0, 1.0 / 3.0f64.sqrt())), EPS));
+
+            let circle = circumcircle(&Vec2(0.0, 0.0), &Vec2(4.0, 0.0), &Vec2(0.0, 3.0)).unwrap();
+            assert!(circle.center.approx_eq(&Vec2(2.0, 1.5), EPS));
+            assert!((circle.radius - 2.5).abs() < EPS);
+
+            assert_eq!(
+                None,
+                circumcircle(&Vec2(0.0, 0.0), &Vec2(1.0, 1.0), &Vec2(3.0, 3.0))
+            );
+            assert_eq!(None, circumcircle(&a, &a, &b));
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use crate::union_find::UnionFind;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+pub fn kruskal(n: usize, edges: &[(usize, usize, i64)]) -> Option<i64> {
+    let mut edges = edges.to_vec();
+    edges.sort_by_key(|&(_, _, w)| w);
+
+    let mut uf = UnionFind::new(n);
+    let mut weight = 0;
+    let mut count = 0;
+    for (a, b, w) in edges {
+        if uf.is_same(a, b) {
+            continue;
+        }
+        uf.merge(a, b);
+        weight += w;
+        count += 1;
+    }
+
+    if count + 1 >= n {
+        Some(weight)
+    } else {
+        None
+    }
+}
+
+pub fn prim(n: usize, adjacency_matrix: &[Vec<Option<i64>>]) -> Option<i64> {
+    assert_eq!(n, adjacency_matrix.len());
+    if n == 0 {
+        return Some(0);
+    }
+
+    let mut used = vec![false; n];
+    let mut min_cost: Vec<Option<i64>> = vec![None; n];
+    min_cost[0] = Some(0);
+    let mut weight = 0;
+    for _ in 0..n {
+        let (v, cost) = (0..n)
+            .filter(|&v| !used[v])
+            .filter_map(|v| min_cost[v].map(|c| (v, c)))
+            .min_by_key(|&(_, c)| c)?;
+        used[v] = true;
+        weight += cost;
+        for (u, &w) in adjacency_matrix[v].iter().enumerate() {
+            if let Some(w) = w {
+                if !used[u] && min_cost[u].is_none_or(|c| w < c) {
+                    min_cost[u] = Some(w);
+                }
+            }
+        }
+    }
+    Some(weight)
+}
+
+pub fn dijkstra(adj: &[Vec<(usize, u64)>], start: usize) -> Vec<u64> {
+    dijkstra_with_prev(adj, start).0
+}
+
+pub fn dijkstra_with_prev(
+    adj: &[Vec<(usize, u64)>],
+    start: usize,
+) -> (Vec<u64>, Vec<Option<usize>>) {
+    let mut dist = vec![u64::MAX; adj.len()];
+    let mut prev = vec![None; adj.len()];
+    let mut heap = BinaryHeap::new();
+    dist[start] = 0;
+    heap.push(Reverse((0, start)));
+    while let Some(Reverse((d, v))) = heap.pop() {
+        if dist[v] < d {
+            continue;
+        }
+        for &(u, w) in adj[v].iter() {
+            let next = match d.checked_add(w) {
+                Some(next) => next,
+                None => continue,
+            };
+            if next < dist[u] {
+                dist[u] = next;
+                prev[u] = Some(v);
+                heap.push(Reverse((next, u)));
+            }
+        }
+    }
+    (dist, prev)
+}
+
+fn bfs_with_prev(adj: &[Vec<usize>], start: usize) -> (Vec<Option<usize>>, Vec<Option<usize>>) {
+    let mut dist = vec![None; adj.len()];
+    let mut prev = vec![None; adj.len()];
+    let mut queue = VecDeque::new();
+    dist[start] = Some(0);
+    queue.push_back(start);
+    while let Some(v) = queue.pop_front() {
+        let d = dist[v].unwrap();
+        for &u in adj[v].iter() {
+            if dist[u].is_none() {
+                dist[u] = Some(d + 1);
+                prev[u] = Some(v);
+                queue.push_back(u);
+            }
+        }
+    }
+    (dist, prev)
+}
+
+pub fn bfs(adj: &[Vec<usize>], start: usize) -> Vec<Option<usize>> {
+    bfs_with_prev(adj, start).0
+}
+
+pub fn bfs_path(adj: &[Vec<usize>], start: usize, goal: usize) -> Option<Vec<usize>> {
+    let (dist, prev) = bfs_with_prev(adj, start);
+    dist[goal]?;
+    let mut path = vec![goal];
+    while let Some(v) = prev[*path.last().unwrap()] {
+        path.push(v);
+    }
+    path.reverse();
+    Some(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bfs, bfs_path, dijkstra, dijkstra_with_prev, kruskal, prim};
+
+    fn to_matrix(n: usize, edges: &[(usize, usize, i64)]) -> Vec<Vec<Option<i64>>> {
+        let mut matrix = vec![vec![None; n]; n];
+        for &(a, b, w) in edges {
+            let w = matrix[a][b].map_or(w, |c: i64| c.min(w));
+            matrix[a][b] = Some(w);
+            matrix[b][a] = Some(w);
+        }
+        matrix
+    }
+
+    #[test]
+    fn test_kruskal() {
+        assert_eq!(Some(0), kruskal(0, &[]));
+        assert_eq!(Some(0), kruskal(1, &[]));
+        assert_eq!(Some(3), kruskal(3, &[(0, 1, 1), (1, 2, 2), (0, 2, 5)]));
+        assert_eq!(None, kruskal(4, &[(0, 1, 1), (2, 3, 2)]));
+    }
+
+    #[test]
+    fn test_prim() {
+        assert_eq!(Some(0), prim(0, &[]));
+        assert_eq!(Some(0), prim(1, &[vec![None]]));
+        assert_eq!(
+            Some(3),
+            prim(3, &to_matrix(3, &[(0, 1, 1), (1, 2, 2), (0, 2, 5)]))
+        );
+        assert_eq!(None, prim(4, &to_matrix(4, &[(0, 1, 1), (2, 3, 2)])));
+    }
+
+    #[test]
+    fn test_prim_dense() {
+        let n = 6;
+        let edges = (0..n)
+            .flat_map(|a| (a + 1..n).map(move |b| (a, b)))
+            .map(|(a, b)| (a, b, ((a * 7 + b * 13) % 11) as i64 - 3))
+            .collect::<Vec<_>>();
+        assert_eq!(kruskal(n, &edges), prim(n, &to_matrix(n, &edges)));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let adj = vec![
+            vec![(1, 4), (2, 1)],
+            vec![(3, 1)],
+            vec![(1, 2), (3, 5)],
+            vec![(4, 3)],
+            vec![],
+        ];
+        assert_eq!(vec![0, 3, 1, 4, 7], dijkstra(&adj, 0));
+        assert_eq!(vec![u64::MAX, 0, u64::MAX, 1, 4], dijkstra(&adj, 1));
+    }
+
+    #[test]
+    fn test_dijkstra_disconnected() {
+        let adj = vec![vec![(1, 2)], vec![(0, 2)], vec![(3, 1)], vec![]];
+        assert_eq!(vec![0, 2, u64::MAX, u64::MAX], dijkstra(&adj, 0));
+        assert_eq!(vec![u64::MAX, u64::MAX, 0, 1], dijkstra(&adj, 2));
+    }
+
+    #[test]
+    fn test_dijkstra_overflowing_path() {
+        let adj = vec![vec![(1, u64::MAX - 1)], vec![(2, 5)], vec![]];
+        assert_eq!(vec![0, u64::MAX - 1, u64::MAX], dijkstra(&adj, 0));
+        let (_, prev) = dijkstra_with_prev(&adj, 0);
+        assert_eq!(vec![None, Some(0), None], prev);
+    }
+
+    #[test]
+    fn test_dijkstra_with_prev() {
+        let adj = vec![
+            vec![(1, 4), (2, 1)],
+            vec![(3, 1)],
+            vec![(1, 2), (3, 5)],
+            vec![(4, 3)],
+            vec![],
+            vec![],
+        ];
+        let (dist, prev) = dijkstra_with_prev(&adj, 0);
+        assert_eq!(vec![0, 3, 1, 4, 7, u64::MAX], dist);
+        assert_eq!(vec![None, Some(2), Some(0), Some(1), Some(3), None], prev);
+
+        let mut path = vec![4];
+        while let Some(v) = prev[*path.last().unwrap()] {
+            path.push(v);
+        }
+        path.reverse();
+        assert_eq!(vec![0, 2, 1, 3, 4], path);
+    }
+
+    #[test]
+    fn test_bfs() {
+        let tree = vec![
+            vec![1, 2],
+            vec![0, 3, 4],
+            vec![0],
+            vec![1],
+            vec![1, 5],
+            vec![4],
+        ];
+        assert_eq!(
+            vec![Some(0), Some(1), Some(1), Some(2), Some(2), Some(3)],
+            bfs(&tree, 0)
+        );
+        assert_eq!(
+            vec![Some(3), Some(2), Some(4), Some(3), Some(1), Some(0)],
+            bfs(&tree, 5)
+        );
+
+        let adj = vec![vec![1], vec![], vec![0]];
+        assert_eq!(vec![Some(0), Some(1), None], bfs(&adj, 0));
+    }
+
+    #[test]
+    fn test_bfs_path() {
+        let tree = vec![
+            vec![1, 2],
+            vec![0, 3, 4],
+            vec![0],
+            vec![1],
+            vec![1, 5],
+            vec![4],
+        ];
+        assert_eq!(Some(vec![2, 0, 1, 4, 5]), bfs_path(&tree, 2, 5));
+        assert_eq!(Some(vec![3]), bfs_path(&tree, 3, 3));
+
+        let adj = vec![vec![1, 2], vec![3], vec![3], vec![4], vec![], vec![]];
+        let path = bfs_path(&adj, 0, 4).unwrap();
+        assert_eq!(4, path.len());
+        assert_eq!(0, path[0]);
+        assert_eq!(4, path[3]);
+        assert!(path.windows(2).all(|w| adj[w[0]].contains(&w[1])));
+        assert_eq!(None, bfs_path(&adj, 0, 5));
+        assert_eq!(None, bfs_path(&adj, 4, 0));
+    }
+}
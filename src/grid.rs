@@ -0,0 +1,69 @@
+const DIRECTIONS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const DIRECTIONS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+fn neighbors(
+    directions: &'static [(isize, isize)],
+    r: usize,
+    c: usize,
+    h: usize,
+    w: usize,
+) -> impl Iterator<Item = (usize, usize)> {
+    directions.iter().filter_map(move |&(dr, dc)| {
+        let nr = r.checked_add_signed(dr).filter(|&nr| nr < h)?;
+        let nc = c.checked_add_signed(dc).filter(|&nc| nc < w)?;
+        Some((nr, nc))
+    })
+}
+
+/// In-bounds cells sharing an edge with `(r, c)` in an `h` x `w` grid.
+pub fn neighbors4(r: usize, c: usize, h: usize, w: usize) -> impl Iterator<Item = (usize, usize)> {
+    neighbors(&DIRECTIONS4, r, c, h, w)
+}
+
+/// In-bounds cells sharing an edge or a corner with `(r, c)` in an `h` x `w` grid.
+pub fn neighbors8(r: usize, c: usize, h: usize, w: usize) -> impl Iterator<Item = (usize, usize)> {
+    neighbors(&DIRECTIONS8, r, c, h, w)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{neighbors4, neighbors8};
+
+    #[test]
+    fn test_neighbors4() {
+        assert_eq!(
+            vec![(0, 1), (1, 0)],
+            neighbors4(0, 0, 3, 4).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(1, 3), (2, 2)],
+            neighbors4(2, 3, 3, 4).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0, 1), (1, 0), (1, 2), (2, 1)],
+            neighbors4(1, 1, 3, 4).collect::<Vec<_>>()
+        );
+        assert_eq!(3, neighbors4(0, 2, 3, 4).count());
+        assert_eq!(0, neighbors4(0, 0, 1, 1).count());
+    }
+
+    #[test]
+    fn test_neighbors8() {
+        assert_eq!(
+            vec![(0, 1), (1, 0), (1, 1)],
+            neighbors8(0, 0, 3, 4).collect::<Vec<_>>()
+        );
+        assert_eq!(8, neighbors8(1, 1, 3, 4).count());
+        assert_eq!(5, neighbors8(1, 3, 3, 4).count());
+        assert_eq!(2, neighbors8(0, 1, 1, 3).count());
+    }
+}
@@ -1,20 +1,53 @@
-use num::{Signed, Zero};
-use std::ops::Add;
+use crate::numeric::{Bounded, Zero};
+use std::ops::{Add, BitXor};
 
 pub trait Monoid: Sized {
     fn identity() -> Self;
     fn apply(&self, rhs: &Self) -> Self;
 }
 
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a `Group`",
+    label = "no inverse for `{Self}`",
+    note = "`Group` needs an inverse; monoids like `Max`/`Min` only implement `Monoid`"
+)]
 pub trait Group: Sized {
     fn identity() -> Self;
     fn inverse(&self) -> Self;
     fn apply(&self, rhs: &Self) -> Self;
 }
 
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not marked `Abelian`",
+    note = "`Abelian` marks a commutative operation; implement it only if `a.apply(&b) == b.apply(&a)`"
+)]
 pub trait Abelian {}
 
-#[derive(Copy, Clone, Debug)]
+/// Marker for monoids where `a.apply(&a) == a`, so overlapping ranges can be combined.
+pub trait Idempotent {}
+
+/// Implements `Monoid` for a tuple newtype over a `Copy` value, e.g.
+/// `impl_monoid!(MyMax, i64::MIN, |a, b| a.max(b))` for `struct MyMax(i64)`.
+/// The identity and the closure body work on the wrapped values.
+#[macro_export]
+macro_rules! impl_monoid {
+    ($t:ty, $identity:expr, |$a:ident, $b:ident| $op:expr) => {
+        impl $crate::group::Monoid for $t {
+            fn identity() -> Self {
+                Self($identity)
+            }
+
+            fn apply(&self, rhs: &Self) -> Self {
+                let ($a, $b) = (self.0, rhs.0);
+                Self($op)
+            }
+        }
+    };
+}
+
+/// Also covers `f32`/`f64`, but inverting a prefix sum cancels catastrophically once
+/// the running total dwarfs the queried range; use `KahanSum` when that matters.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub struct Sum<T>(pub T);
 
 impl<T: Copy + Clone + Zero + Add<Output = T>> Monoid for Sum<T> {
@@ -27,30 +60,191 @@ impl<T: Copy + Clone + Zero + Add<Output = T>> Monoid for Sum<T> {
     }
 }
 
-impl<T: Copy + Clone + Add<Output = T> + Zero + Signed> Group for Sum<T> {
-    fn identity() -> Self {
+macro_rules! impl_sum_group {
+    ($($t:ty),*) => {$(
+        impl Group for Sum<$t> {
+            fn identity() -> Self {
+                Self(0 as $t)
+            }
+
+            fn inverse(&self) -> Self {
+                Self(-self.0)
+            }
+
+            fn apply(&self, rhs: &Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+    )*};
+}
+
+/// Unsigned sums form a group modulo `2^bits`, so range queries that subtract
+/// prefix sums still produce the exact result as long as that result fits.
+macro_rules! impl_wrapping_sum_group {
+    ($($t:ty),*) => {$(
+        impl Group for Sum<$t> {
+            fn identity() -> Self {
+                Self(0)
+            }
+
+            fn inverse(&self) -> Self {
+                Self(self.0.wrapping_neg())
+            }
+
+            fn apply(&self, rhs: &Self) -> Self {
+                Self(self.0.wrapping_add(rhs.0))
+            }
+        }
+    )*};
+}
+
+impl_sum_group!(i8, i16, i32, i64, i128, isize, f32, f64);
+impl_wrapping_sum_group!(u8, u16, u32, u64, u128, usize);
+
+impl<T: Add<Output = T>> Abelian for Sum<T> {}
+
+impl<T> From<T> for Sum<T> {
+    fn from(x: T) -> Self {
+        Sum(x)
+    }
+}
+
+impl<T: Zero> Default for Sum<T> {
+    fn default() -> Self {
         Self(T::zero())
     }
+}
+
+/// Compensated `f64` sum: `apply` tracks the rounding error lost by each addition.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct KahanSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl KahanSum {
+    pub fn value(&self) -> f64 {
+        self.sum + self.compensation
+    }
+}
+
+impl Monoid for KahanSum {
+    fn identity() -> Self {
+        Self::default()
+    }
+
+    fn apply(&self, rhs: &Self) -> Self {
+        let sum = self.sum + rhs.sum;
+        let rhs_part = sum - self.sum;
+        let error = (self.sum - (sum - rhs_part)) + (rhs.sum - rhs_part);
+        Self {
+            sum,
+            compensation: self.compensation + rhs.compensation + error,
+        }
+    }
+}
+
+impl Group for KahanSum {
+    fn identity() -> Self {
+        Self::default()
+    }
 
     fn inverse(&self) -> Self {
-        Self(-self.0)
+        Self {
+            sum: -self.sum,
+            compensation: -self.compensation,
+        }
     }
 
     fn apply(&self, rhs: &Self) -> Self {
-        Self(self.0 + rhs.0)
+        Monoid::apply(self, rhs)
     }
 }
 
-impl<T: Add<Output = T>> Abelian for Sum<T> {}
+impl Abelian for KahanSum {}
 
-impl<T> From<T> for Sum<T> {
+impl From<f64> for KahanSum {
+    fn from(x: f64) -> Self {
+        Self {
+            sum: x,
+            compensation: 0.0,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Max<T>(pub T);
+
+impl<T: Copy + Ord + Bounded> Monoid for Max<T> {
+    fn identity() -> Self {
+        Self(T::min_value())
+    }
+
+    fn apply(&self, rhs: &Self) -> Self {
+        Self(std::cmp::max(self.0, rhs.0))
+    }
+}
+
+impl<T> Idempotent for Max<T> {}
+
+impl<T> From<T> for Max<T> {
     fn from(x: T) -> Self {
-        Sum(x)
+        Max(x)
     }
 }
 
-impl<T: Zero> Default for Sum<T> {
-    fn default() -> Self {
+#[derive(Copy, Clone, Debug)]
+pub struct Min<T>(pub T);
+
+impl<T: Copy + Ord + Bounded> Monoid for Min<T> {
+    fn identity() -> Self {
+        Self(T::max_value())
+    }
+
+    fn apply(&self, rhs: &Self) -> Self {
+        Self(std::cmp::min(self.0, rhs.0))
+    }
+}
+
+impl<T> Idempotent for Min<T> {}
+
+impl<T> From<T> for Min<T> {
+    fn from(x: T) -> Self {
+        Min(x)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Xor<T>(pub T);
+
+impl<T: Copy + Zero + BitXor<Output = T>> Monoid for Xor<T> {
+    fn identity() -> Self {
+        Self(T::zero())
+    }
+
+    fn apply(&self, rhs: &Self) -> Self {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl<T: Copy + Zero + BitXor<Output = T>> Group for Xor<T> {
+    fn identity() -> Self {
         Self(T::zero())
     }
+
+    fn inverse(&self) -> Self {
+        *self
+    }
+
+    fn apply(&self, rhs: &Self) -> Self {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl<T: BitXor<Output = T>> Abelian for Xor<T> {}
+
+impl<T> From<T> for Xor<T> {
+    fn from(x: T) -> Self {
+        Xor(x)
+    }
 }
@@ -1,30 +1,73 @@
-use std::fmt::Debug;
-use std::io::{stdin, Read, Stdin};
+use std::fmt::{Debug, Display, Formatter};
+use std::io::{stdin, BufRead, BufReader, Read, Stdin};
 use std::str::FromStr;
 
-static mut _INPUT_BUF: String = String::new();
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum InputError {
+    EndOfInput,
+    Parse(String),
+}
 
-fn write_buf<T: Read>(source: &mut T) {
-    unsafe {
-        source.read_to_string(&mut _INPUT_BUF).unwrap();
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Self::EndOfInput => write!(f, "unexpected end of input"),
+            Self::Parse(token) => write!(f, "failed to parse token {:?}", token),
+        }
     }
 }
 
-fn read_buf() -> &'static str {
-    unsafe { &_INPUT_BUF }
+impl std::error::Error for InputError {}
+
+/// Tokenizer over any `BufRead`. `Input` is the common case of wrapping a plain
+/// `Read` source, which can either be slurped up front or read lazily.
+pub struct BufInput<R: BufRead> {
+    source: R,
+    buf: String,
+    pos: usize,
+    lazy: bool,
+    at_start: bool,
 }
 
-pub struct Input<T: Read> {
-    #[allow(dead_code)]
-    source: T,
-    iter: Box<dyn Iterator<Item = &'static str>>,
+pub type Input<T> = BufInput<BufReader<T>>;
+
+/// Skipped when it opens the input. `\r` needs no such care, being ASCII whitespace.
+const BOM: char = '\u{FEFF}';
+
+impl<T: Read> BufInput<BufReader<T>> {
+    pub fn new(source: T) -> Self {
+        let mut source = BufReader::new(source);
+        let mut buf = String::new();
+        source.read_to_string(&mut buf).unwrap();
+        let pos = if buf.starts_with(BOM) {
+            BOM.len_utf8()
+        } else {
+            0
+        };
+        Self {
+            source,
+            buf,
+            pos,
+            lazy: false,
+            at_start: false,
+        }
+    }
+
+    pub fn interactive(source: T) -> Self {
+        Self::lazy(BufReader::new(source))
+    }
 }
 
-impl<T: Read> Input<T> {
-    pub fn new(mut source: T) -> Self {
-        write_buf(&mut source);
-        let iter = Box::new(read_buf().split_ascii_whitespace());
-        Self { source, iter }
+impl<R: BufRead> BufInput<R> {
+    /// Reads one line at a time, only when the current line runs out of tokens.
+    pub fn lazy(source: R) -> Self {
+        Self {
+            source,
+            buf: String::new(),
+            pos: 0,
+            lazy: true,
+            at_start: true,
+        }
     }
 
     pub fn read<U>(&mut self) -> U
@@ -32,7 +75,131 @@ impl<T: Read> Input<T> {
         U: FromStr,
         U::Err: Debug,
     {
-        self.iter.next().unwrap().parse().unwrap()
+        self.try_read().unwrap()
+    }
+
+    fn next_token_bytes(&mut self) -> &[u8] {
+        let end = self.token_end().expect("unexpected end of input");
+        let start = self.pos;
+        self.pos = end;
+        &self.buf.as_bytes()[start..end]
+    }
+
+    /// Parses the next token as `i64` straight from its bytes, skipping `str::parse`.
+    pub fn read_int(&mut self) -> i64 {
+        let token = self.next_token_bytes();
+        let (negative, digits) = match token.split_first() {
+            Some((b'-', rest)) => (true, rest),
+            Some((b'+', rest)) => (false, rest),
+            _ => (false, token),
+        };
+        assert!(!digits.is_empty(), "failed to parse token {:?}", token);
+        let mut value: i64 = 0;
+        for &b in digits {
+            assert!(b.is_ascii_digit(), "failed to parse token {:?}", token);
+            let digit = (b - b'0') as i64;
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(if negative { -digit } else { digit }))
+                .unwrap_or_else(|| panic!("failed to parse token {:?}", token));
+        }
+        value
+    }
+
+    pub fn read_uint(&mut self) -> u64 {
+        let token = self.next_token_bytes();
+        let digits = token.strip_prefix(b"+").unwrap_or(token);
+        assert!(!digits.is_empty(), "failed to parse token {:?}", token);
+        digits.iter().fold(0u64, |value, &b| {
+            assert!(b.is_ascii_digit(), "failed to parse token {:?}", token);
+            value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add((b - b'0') as u64))
+                .unwrap_or_else(|| panic!("failed to parse token {:?}", token))
+        })
+    }
+
+    pub fn read_bytes(&mut self) -> Vec<u8> {
+        self.next_token_bytes().to_vec()
+    }
+
+    pub fn read_array<U, const N: usize>(&mut self) -> [U; N]
+    where
+        U: FromStr + Default + Copy,
+        U::Err: Debug,
+    {
+        let mut array = [U::default(); N];
+        for e in array.iter_mut() {
+            *e = self.read();
+        }
+        array
+    }
+
+    pub fn read_matrix<U>(&mut self, rows: usize, cols: usize) -> Vec<Vec<U>>
+    where
+        U: FromStr,
+        U::Err: Debug,
+    {
+        (0..rows)
+            .map(|_| (0..cols).map(|_| self.read()).collect())
+            .collect()
+    }
+
+    pub fn read_all<U>(&mut self) -> Vec<U>
+    where
+        U: FromStr,
+        U::Err: Debug,
+    {
+        let mut values = vec![];
+        while self.has_next() {
+            values.push(self.read());
+        }
+        values
+    }
+
+    pub fn try_read<U: FromStr>(&mut self) -> Result<U, InputError> {
+        let end = self.token_end().ok_or(InputError::EndOfInput)?;
+        let token = &self.buf[self.pos..end];
+        self.pos = end;
+        token
+            .parse()
+            .map_err(|_| InputError::Parse(token.to_string()))
+    }
+
+    pub fn peek(&mut self) -> Option<&str> {
+        let end = self.token_end()?;
+        Some(&self.buf[self.pos..end])
+    }
+
+    pub fn has_next(&mut self) -> bool {
+        self.peek().is_some()
+    }
+
+    fn token_end(&mut self) -> Option<usize> {
+        loop {
+            let bytes = self.buf.as_bytes();
+            while self.pos < bytes.len() && bytes[self.pos].is_ascii_whitespace() {
+                self.pos += 1;
+            }
+            if self.pos < bytes.len() {
+                let len = bytes[self.pos..]
+                    .iter()
+                    .position(|b| b.is_ascii_whitespace())
+                    .unwrap_or(bytes.len() - self.pos);
+                return Some(self.pos + len);
+            }
+            if !self.lazy {
+                return None;
+            }
+            self.buf.clear();
+            self.pos = 0;
+            if self.source.read_line(&mut self.buf).unwrap() == 0 {
+                return None;
+            }
+            if std::mem::take(&mut self.at_start) && self.buf.starts_with(BOM) {
+                self.pos = BOM.len_utf8();
+            }
+        }
     }
 }
 
@@ -41,3 +208,259 @@ impl Default for Input<Stdin> {
         Self::new(stdin())
     }
 }
+
+/// Reads one value per listed type, e.g. `read!(input, usize, i64, String)`.
+/// A single type yields the bare value; several yield a tuple.
+#[macro_export]
+macro_rules! read {
+    ($input:expr, $t:ty) => {
+        $input.read::<$t>()
+    };
+    ($input:expr, $($t:ty),+ $(,)?) => {
+        ($($input.read::<$t>(),)+)
+    };
+}
+
+#[cfg(test)]
+mod test {
+    mod input {
+        use super::super::{BufInput, Input, InputError};
+        use std::io::Cursor;
+
+        #[test]
+        fn test_read() {
+            let mut input = Input::new("5 -3 hello\n2.5".as_bytes());
+            assert_eq!(5usize, input.read());
+            assert_eq!(-3i64, input.read());
+            assert_eq!("hello".to_string(), input.read::<String>());
+            assert_eq!(2.5f64, input.read());
+        }
+
+        #[test]
+        #[should_panic]
+        fn test_read_end_of_input() {
+            let mut input = Input::new("".as_bytes());
+            input.read::<i64>();
+        }
+
+        #[test]
+        fn test_try_read() {
+            let mut input = Input::new("10 abc".as_bytes());
+            assert_eq!(Ok(10i64), input.try_read());
+            assert_eq!(
+                Err(InputError::Parse("abc".to_string())),
+                input.try_read::<i64>()
+            );
+            assert_eq!(Err(InputError::EndOfInput), input.try_read::<i64>());
+        }
+
+        #[test]
+        fn test_peek() {
+            let mut input = Input::new("1 2".as_bytes());
+            assert_eq!(Some("1"), input.peek());
+            assert_eq!(Some("1"), input.peek());
+            assert_eq!(1, input.read::<i32>());
+            assert_eq!(Some("2"), input.peek());
+            assert_eq!(2, input.read::<i32>());
+            assert_eq!(None, input.peek());
+        }
+
+        #[test]
+        fn test_has_next() {
+            let mut input = Input::new("3 1 4 1 5\n9\n".as_bytes());
+            let mut v = vec![];
+            while input.has_next() {
+                v.push(input.read::<i32>());
+            }
+            assert_eq!(vec![3, 1, 4, 1, 5, 9], v);
+            assert!(!input.has_next());
+
+            let mut input = Input::new(" \n ".as_bytes());
+            assert!(!input.has_next());
+        }
+
+        #[test]
+        fn test_interactive() {
+            let mut input = Input::interactive("1 2\n\n3\n".as_bytes());
+            assert_eq!(1, input.read::<i32>());
+            assert_eq!(Some("2"), input.peek());
+            assert_eq!(2, input.read::<i32>());
+            assert!(input.has_next());
+            assert_eq!(3, input.read::<i32>());
+            assert!(!input.has_next());
+            assert_eq!(Err(InputError::EndOfInput), input.try_read::<i32>());
+        }
+
+        #[test]
+        fn test_read_macro() {
+            let mut input = Input::new("5 -3 hello\n7 x".as_bytes());
+            assert_eq!(
+                (5usize, -3i64, "hello".to_string()),
+                crate::read!(input, usize, i64, String)
+            );
+            assert_eq!(7u32, crate::read!(input, u32));
+            let (c,) = crate::read!(input, char,);
+            assert_eq!('x', c);
+        }
+
+        #[test]
+        fn test_read_macro_through_reference() {
+            fn solve<T: std::io::Read>(input: &mut Input<T>) -> (usize, i64) {
+                let (n, x) = crate::read!(input, usize, i64);
+                (n + 1, x * 2)
+            }
+
+            let mut input = Input::new("3 -4 9".as_bytes());
+            assert_eq!((4, -8), solve(&mut input));
+            assert_eq!(9, crate::read!(input, i32));
+        }
+
+        #[test]
+        fn test_read_array() {
+            let mut input = Input::new("1 2 3\n4.5 -1".as_bytes());
+            assert_eq!([1i64, 2, 3], input.read_array::<i64, 3>());
+            assert_eq!([4.5, -1.0], input.read_array());
+            assert_eq!([0u8; 0], input.read_array());
+        }
+
+        #[test]
+        #[should_panic]
+        fn test_read_array_parse_error() {
+            let mut input = Input::new("1 x 3".as_bytes());
+            input.read_array::<i64, 3>();
+        }
+
+        #[test]
+        fn test_read_matrix() {
+            let mut input = Input::new("1 2 3\n4 5 6\n7".as_bytes());
+            let matrix = input.read_matrix::<i32>(2, 3);
+            assert_eq!(2, matrix.len());
+            assert!(matrix.iter().all(|row| row.len() == 3));
+            assert_eq!(vec![vec![1, 2, 3], vec![4, 5, 6]], matrix);
+            assert!(input.read_matrix::<i32>(0, 3).is_empty());
+            assert_eq!(vec![Vec::<i32>::new(); 2], input.read_matrix(2, 0));
+            assert_eq!(7, input.read::<i32>());
+        }
+
+        #[test]
+        fn test_read_all() {
+            let mut input = Input::new("4\n1 2 3\n\n4 5  \n".as_bytes());
+            assert_eq!(4, input.read::<usize>());
+            assert_eq!(15, input.read_all::<i64>().iter().sum::<i64>());
+            assert!(input.read_all::<i64>().is_empty());
+
+            let mut input = Input::interactive("a b\nc\n".as_bytes());
+            assert_eq!(vec!["a", "b", "c"], input.read_all::<String>());
+        }
+
+        #[test]
+        fn test_buf_input() {
+            let mut input = BufInput::lazy(Cursor::new("1 2\n  \nx 3.5\n"));
+            assert_eq!(1, input.read::<i32>());
+            assert_eq!(Some("2"), input.peek());
+            assert_eq!(2, input.read::<i32>());
+            assert_eq!('x', input.read::<char>());
+            assert_eq!(vec![3.5], input.read_all::<f64>());
+            assert_eq!(Err(InputError::EndOfInput), input.try_read::<i32>());
+
+            let mut cursor = Cursor::new("10 20\n30\n40\n");
+            {
+                let mut input = BufInput::lazy(&mut cursor);
+                assert_eq!(10, input.read::<i32>());
+            }
+            assert_eq!(6, cursor.position());
+            {
+                let mut input = BufInput::lazy(&mut cursor);
+                assert_eq!(30, input.read::<i32>());
+            }
+            assert_eq!(9, cursor.position());
+        }
+
+        #[test]
+        fn test_read_int() {
+            let values = [
+                "0",
+                "7",
+                "-7",
+                "+42",
+                "1000000007",
+                "-000123",
+                &i64::MAX.to_string(),
+                &i64::MIN.to_string(),
+            ]
+            .join(" \n ");
+            let mut fast = Input::new(values.as_bytes());
+            let mut slow = Input::new(values.as_bytes());
+            while slow.has_next() {
+                assert_eq!(slow.read::<i64>(), fast.read_int());
+            }
+            assert!(!fast.has_next());
+
+            let mut input = Input::new("0 +5 18446744073709551615".as_bytes());
+            assert_eq!(0, input.read_uint());
+            assert_eq!(5, input.read_uint());
+            assert_eq!(u64::MAX, input.read_uint());
+        }
+
+        #[test]
+        #[should_panic]
+        fn test_read_int_invalid() {
+            Input::new("12a".as_bytes()).read_int();
+        }
+
+        #[test]
+        #[should_panic(expected = "failed to parse token")]
+        fn test_read_int_overflow() {
+            Input::new("99999999999999999999".as_bytes()).read_int();
+        }
+
+        #[test]
+        #[should_panic(expected = "failed to parse token")]
+        fn test_read_int_underflow() {
+            Input::new("-9223372036854775809".as_bytes()).read_int();
+        }
+
+        #[test]
+        #[should_panic(expected = "failed to parse token")]
+        fn test_read_uint_overflow() {
+            Input::new("18446744073709551616".as_bytes()).read_uint();
+        }
+
+        #[test]
+        #[should_panic]
+        fn test_read_uint_negative() {
+            Input::new("-1".as_bytes()).read_uint();
+        }
+
+        #[test]
+        fn test_read_bytes() {
+            let mut input = Input::new("abc\n  x1y 42 \u{3042}".as_bytes());
+            assert_eq!(vec![b'a', b'b', b'c'], input.read_bytes());
+            assert_eq!(b"x1y".to_vec(), input.read_bytes());
+            assert_eq!(42, input.read::<i32>());
+            assert_eq!("\u{3042}".as_bytes(), &input.read_bytes()[..]);
+            assert!(!input.has_next());
+        }
+
+        #[test]
+        fn test_bom_and_crlf() {
+            let source = "\u{FEFF}3 -1\r\nabc\r\n\r\n42\r\n";
+            let mut input = Input::new(source.as_bytes());
+            assert_eq!(3, input.read_int());
+            assert_eq!(-1, input.read::<i64>());
+            assert_eq!("abc", input.read::<String>());
+            assert_eq!(42, input.read_uint());
+            assert!(!input.has_next());
+
+            let mut input = Input::interactive(source.as_bytes());
+            assert_eq!(3, input.read::<u32>());
+            assert_eq!(-1, input.read_int());
+            assert_eq!(b"abc".to_vec(), input.read_bytes());
+            assert_eq!(42, input.read::<u8>());
+            assert!(!input.has_next());
+
+            let mut input = Input::interactive("\r\n\u{FEFF}x".as_bytes());
+            assert_eq!("\u{FEFF}x", input.read::<String>());
+        }
+    }
+}
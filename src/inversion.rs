@@ -0,0 +1,74 @@
+use crate::binary_indexed_tree::BIT;
+use crate::compress::compress;
+use crate::group::Sum;
+
+pub fn count_inversions<T: Ord + Clone>(data: &[T]) -> u64 {
+    let (ranks, unique) = compress(data);
+    let mut bit = BIT::<Sum<i64>>::new(unique.len());
+    let mut count = 0;
+    for (i, rank) in ranks.into_iter().enumerate() {
+        count += i as u64 - bit.query(..=rank).0 as u64;
+        bit.add(rank, 1);
+    }
+    count
+}
+
+pub fn adjacent_swaps_to_sort(a: &[usize]) -> u64 {
+    count_inversions(a)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{adjacent_swaps_to_sort, count_inversions};
+    use rand::rngs::SmallRng;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
+
+    fn brute_force<T: Ord>(data: &[T]) -> u64 {
+        let n = data.len();
+        (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .filter(|&(i, j)| data[i] > data[j])
+            .count() as u64
+    }
+
+    #[test]
+    fn test_count_inversions() {
+        assert_eq!(0, count_inversions::<i64>(&[]));
+        assert_eq!(0, count_inversions(&[1]));
+        assert_eq!(1, count_inversions(&[2, 1]));
+        assert_eq!(5, count_inversions(&[3, 1, 2, 0]));
+        assert_eq!(0, count_inversions(&[1, 1, 1]));
+        assert_eq!(2, count_inversions(&["b", "c", "a"]));
+    }
+
+    #[test]
+    fn test_count_inversions_random() {
+        let mut rng = SmallRng::seed_from_u64(843);
+        for n in 0..50usize {
+            let mut perm = (0..n).collect::<Vec<_>>();
+            assert_eq!(0, count_inversions(&perm));
+            perm.reverse();
+            assert_eq!(
+                (n * n.saturating_sub(1) / 2) as u64,
+                count_inversions(&perm)
+            );
+            perm.shuffle(&mut rng);
+            assert_eq!(brute_force(&perm), count_inversions(&perm));
+
+            let data = (0..n)
+                .map(|_| rng.gen_range(-1_000_000_000i64, 1_000_000_000))
+                .collect::<Vec<_>>();
+            assert_eq!(brute_force(&data), count_inversions(&data));
+        }
+    }
+
+    #[test]
+    fn test_adjacent_swaps_to_sort() {
+        assert_eq!(0, adjacent_swaps_to_sort(&[0, 1, 2, 3, 4]));
+        assert_eq!(10, adjacent_swaps_to_sort(&[4, 3, 2, 1, 0]));
+        assert_eq!(0, adjacent_swaps_to_sort(&[1, 1, 2, 2]));
+        assert_eq!(4, adjacent_swaps_to_sort(&[2, 2, 1, 1]));
+        assert_eq!(4, adjacent_swaps_to_sort(&[3, 1, 2, 1]));
+    }
+}
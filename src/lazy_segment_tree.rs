@@ -0,0 +1,231 @@
+use crate::group::Monoid;
+use crate::range::bounds;
+use std::ops::RangeBounds;
+
+/// An action on a monoid. `f.apply(&g)` composes the actions so that `f` acts first.
+pub trait Action<M: Monoid>: Monoid {
+    fn act(&self, value: &M) -> M;
+}
+
+#[derive(Clone, Debug)]
+pub struct LazySegmentTree<M: Monoid + Clone, A: Action<M> + Clone> {
+    len: usize,
+    size: usize,
+    tree: Vec<M>,
+    lazy: Vec<A>,
+}
+
+impl<M: Monoid + Clone, A: Action<M> + Clone> LazySegmentTree<M, A> {
+    pub fn new(n: usize) -> Self {
+        let size = n.next_power_of_two();
+        Self {
+            len: n,
+            size,
+            tree: (0..2 * size).map(|_| M::identity()).collect::<Vec<_>>(),
+            lazy: (0..2 * size).map(|_| A::identity()).collect::<Vec<_>>(),
+        }
+    }
+
+    pub fn from_slice<U: Clone + Into<M>>(v: &[U]) -> Self {
+        let mut ret = Self::new(v.len());
+        for (i, e) in v.iter().cloned().enumerate() {
+            ret.tree[ret.size + i] = e.into();
+        }
+        for i in (1..ret.size).rev() {
+            ret.tree[i] = ret.tree[i * 2].apply(&ret.tree[i * 2 + 1]);
+        }
+        ret
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn apply_range(&mut self, range: impl RangeBounds<usize>, action: impl Into<A>) {
+        let (begin, end) = bounds(range, self.len());
+        self.update(1, 0, self.size, begin, end, &action.into());
+    }
+
+    pub fn query(&mut self, range: impl RangeBounds<usize>) -> M {
+        let (begin, end) = bounds(range, self.len());
+        self.fold(1, 0, self.size, begin, end)
+    }
+
+    fn push(&mut self, node: usize) {
+        for child in node * 2..node * 2 + 2 {
+            self.tree[child] = self.lazy[node].act(&self.tree[child]);
+            self.lazy[child] = self.lazy[child].apply(&self.lazy[node]);
+        }
+        self.lazy[node] = A::identity();
+    }
+
+    fn update(&mut self, node: usize, l: usize, r: usize, begin: usize, end: usize, action: &A) {
+        if end <= l || r <= begin {
+            return;
+        }
+        if begin <= l && r <= end {
+            self.tree[node] = action.act(&self.tree[node]);
+            self.lazy[node] = self.lazy[node].apply(action);
+            return;
+        }
+        self.push(node);
+        let m = (l + r) / 2;
+        self.update(node * 2, l, m, begin, end, action);
+        self.update(node * 2 + 1, m, r, begin, end, action);
+        self.tree[node] = self.tree[node * 2].apply(&self.tree[node * 2 + 1]);
+    }
+
+    fn fold(&mut self, node: usize, l: usize, r: usize, begin: usize, end: usize) -> M {
+        if end <= l || r <= begin {
+            return M::identity();
+        }
+        if begin <= l && r <= end {
+            return self.tree[node].clone();
+        }
+        self.push(node);
+        let m = (l + r) / 2;
+        self.fold(node * 2, l, m, begin, end)
+            .apply(&self.fold(node * 2 + 1, m, r, begin, end))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod lazy_segment_tree {
+        use super::super::{Action, LazySegmentTree};
+        use crate::group::{Min, Monoid};
+        use rand::rngs::SmallRng;
+        use rand::{Rng, SeedableRng};
+
+        #[derive(Copy, Clone, Debug)]
+        struct RangeSum {
+            sum: i64,
+            len: i64,
+        }
+
+        impl Monoid for RangeSum {
+            fn identity() -> Self {
+                RangeSum { sum: 0, len: 0 }
+            }
+
+            fn apply(&self, rhs: &Self) -> Self {
+                RangeSum {
+                    sum: self.sum + rhs.sum,
+                    len: self.len + rhs.len,
+                }
+            }
+        }
+
+        impl From<i64> for RangeSum {
+            fn from(x: i64) -> Self {
+                RangeSum { sum: x, len: 1 }
+            }
+        }
+
+        #[derive(Copy, Clone, Debug)]
+        struct Add(i64);
+
+        impl Monoid for Add {
+            fn identity() -> Self {
+                Add(0)
+            }
+
+            fn apply(&self, rhs: &Self) -> Self {
+                Add(self.0 + rhs.0)
+            }
+        }
+
+        impl Action<RangeSum> for Add {
+            fn act(&self, value: &RangeSum) -> RangeSum {
+                RangeSum {
+                    sum: value.sum + self.0 * value.len,
+                    len: value.len,
+                }
+            }
+        }
+
+        #[derive(Copy, Clone, Debug)]
+        struct Assign(Option<i64>);
+
+        impl Monoid for Assign {
+            fn identity() -> Self {
+                Assign(None)
+            }
+
+            fn apply(&self, rhs: &Self) -> Self {
+                Assign(rhs.0.or(self.0))
+            }
+        }
+
+        impl Action<Min<i64>> for Assign {
+            fn act(&self, value: &Min<i64>) -> Min<i64> {
+                self.0.map_or(*value, Min)
+            }
+        }
+
+        fn random_range(rng: &mut SmallRng, n: usize) -> (usize, usize) {
+            let l = rng.gen_range(0, n + 1);
+            let r = rng.gen_range(0, n + 1);
+            (l.min(r), l.max(r))
+        }
+
+        #[test]
+        fn test_range_add_range_sum() {
+            let mut rng = SmallRng::seed_from_u64(818);
+            for n in 0..20 {
+                let mut data = (0..n)
+                    .map(|_| rng.gen_range(-100, 100))
+                    .collect::<Vec<i64>>();
+                let mut tree = LazySegmentTree::<RangeSum, Add>::from_slice(&data);
+                for _ in 0..50 {
+                    let (l, r) = random_range(&mut rng, n);
+                    if rng.gen() {
+                        let x = rng.gen_range(-100, 100);
+                        data[l..r].iter_mut().for_each(|e| *e += x);
+                        tree.apply_range(l..r, Add(x));
+                    } else {
+                        let expected = data[l..r].iter().sum::<i64>();
+                        assert_eq!(expected, tree.query(l..r).sum);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_range_assign_range_min() {
+            let mut rng = SmallRng::seed_from_u64(1818);
+            for n in 0..20 {
+                let mut data = (0..n)
+                    .map(|_| rng.gen_range(-100, 100))
+                    .collect::<Vec<i64>>();
+                let mut tree = LazySegmentTree::<Min<i64>, Assign>::from_slice(&data);
+                for _ in 0..50 {
+                    let (l, r) = random_range(&mut rng, n);
+                    if rng.gen() {
+                        let x = rng.gen_range(-100, 100);
+                        data[l..r].iter_mut().for_each(|e| *e = x);
+                        tree.apply_range(l..r, Assign(Some(x)));
+                    } else {
+                        let expected = *data[l..r].iter().min().unwrap_or(&i64::MAX);
+                        assert_eq!(expected, tree.query(l..r).0);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_bounds() {
+            let mut tree = LazySegmentTree::<RangeSum, Add>::from_slice(&[1i64, 2, 3, 4, 5]);
+            tree.apply_range(1..=3, Add(10));
+            assert_eq!(45, tree.query(..).sum);
+            assert_eq!(13, tree.query(..2).sum);
+            assert_eq!(19, tree.query(3..).sum);
+            assert_eq!(0, tree.query(2..2).sum);
+            assert_eq!(5, tree.len());
+        }
+    }
+}
@@ -4,7 +4,26 @@ pub mod output;
 
 pub mod binary_indexed_tree;
 pub mod binary_search;
+pub mod combinatorics;
+pub mod compress;
+pub mod fraction;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
 pub mod group;
+pub mod inversion;
+pub mod lazy_segment_tree;
+pub mod math;
+pub mod mo;
+pub mod numeric;
+pub mod permutation;
+pub mod prefix_sum;
 pub mod prime;
+mod range;
+pub mod run_length;
+pub mod segment_tree;
+pub mod sliding_window;
+pub mod sparse_table;
+pub mod subset;
 pub mod union_find;
 pub mod vector;
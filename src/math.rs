@@ -0,0 +1,410 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+pub trait UInt:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn from_usize(n: usize) -> Self;
+    fn as_usize(self) -> usize;
+}
+
+macro_rules! impl_uint {
+    ($($t:ty),*) => {$(
+        impl UInt for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn from_usize(n: usize) -> Self {
+                n as $t
+            }
+
+            fn as_usize(self) -> usize {
+                self as usize
+            }
+        }
+    )*};
+}
+
+impl_uint!(u8, u16, u32, u64, u128, usize);
+
+pub fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        (a / gcd(a, b) * b).abs()
+    }
+}
+
+/// Returns `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`.
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if b == 0 {
+        if a < 0 {
+            (-a, -1, 0)
+        } else {
+            (a, 1, 0)
+        }
+    } else {
+        let (g, x, y) = ext_gcd(b, a % b);
+        (g, y, x - a / b * y)
+    }
+}
+
+/// Combines `x ≡ r1 (mod m1)` and `x ≡ r2 (mod m2)` into `x ≡ r (mod lcm(m1, m2))`,
+/// returning `(r, lcm)` with `0 <= r < lcm`.
+pub fn crt(r1: i64, m1: i64, r2: i64, m2: i64) -> Option<(i64, i64)> {
+    assert!(m1 > 0 && m2 > 0);
+    let (g, p, _) = ext_gcd(m1, m2);
+    let diff = r2 - r1;
+    if diff % g != 0 {
+        return None;
+    }
+    let step = m2 / g;
+    let lcm = m1 / g * m2;
+    let t = (diff / g) as i128 * p as i128 % step as i128;
+    let r = (r1 as i128 + m1 as i128 * t).rem_euclid(lcm as i128);
+    Some((r as i64, lcm))
+}
+
+pub fn crt_many(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(r, m), &(ri, mi)| crt(r, m, ri, mi))
+}
+
+pub fn isqrt(n: u64) -> u64 {
+    let mut x = (n as f64).sqrt() as u64;
+    while x.checked_mul(x).is_none_or(|sq| sq > n) {
+        x -= 1;
+    }
+    while (x + 1).checked_mul(x + 1).is_some_and(|sq| sq <= n) {
+        x += 1;
+    }
+    x
+}
+
+pub fn is_perfect_square(n: u64) -> bool {
+    let x = isqrt(n);
+    x * x == n
+}
+
+pub fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
+    let m = modulus as u128;
+    let mut base = base as u128 % m;
+    let mut exp = exp;
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+pub fn mod_inv(a: u64, modulus: u64) -> Option<u64> {
+    // Run the extended Euclid in i128 so moduli above `i64::MAX` are handled.
+    let (mut r0, mut r1) = (modulus as i128, (a % modulus) as i128);
+    let (mut x0, mut x1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        let r = r0 - q * r1;
+        r0 = r1;
+        r1 = r;
+        let x = x0 - q * x1;
+        x0 = x1;
+        x1 = x;
+    }
+    if r0 == 1 {
+        Some(x0.rem_euclid(modulus as i128) as u64)
+    } else {
+        None
+    }
+}
+
+/// Smallest `x` with `base^x ≡ target (mod modulus)`; `base` need not be coprime to `modulus`.
+pub fn discrete_log(base: u64, target: u64, modulus: u64) -> Option<u64> {
+    let mul = |a: u64, b: u64, m: u64| (a as u128 * b as u128 % m as u128) as u64;
+    let mut m = modulus;
+    let a = base % m;
+    let mut b = target % m;
+    let mut k = 1 % m;
+    let mut offset = 0;
+    loop {
+        let g = a.gcd(m);
+        if g == 1 {
+            break;
+        }
+        if b == k {
+            return Some(offset);
+        }
+        if !b.is_multiple_of(g) {
+            return None;
+        }
+        b /= g;
+        m /= g;
+        offset += 1;
+        k = mul(k, a / g, m);
+    }
+
+    let n = (m as f64).sqrt() as u64 + 1;
+    let mut baby = HashMap::new();
+    let mut cur = b;
+    for q in 0..=n {
+        baby.insert(cur, q);
+        cur = mul(cur, a, m);
+    }
+    let giant = mod_pow(a, n, m);
+    let mut cur = k;
+    for p in 1..=n {
+        cur = mul(cur, giant, m);
+        if let Some(&q) = baby.get(&cur) {
+            return Some(n * p - q + offset);
+        }
+    }
+    None
+}
+
+/// Method forms of `gcd`, `lcm` and `mod_pow` for every primitive integer.
+/// `gcd`/`lcm` are non-negative; `mod_pow` needs a positive modulus that fits in `u64`.
+pub trait IntExt: Sized {
+    fn gcd(self, other: Self) -> Self;
+    fn lcm(self, other: Self) -> Self;
+    fn mod_pow(self, exp: u64, modulus: Self) -> Self;
+}
+
+macro_rules! impl_int_ext_signed {
+    ($($t:ty),*) => {$(
+        impl IntExt for $t {
+            fn gcd(self, other: Self) -> Self {
+                let (mut a, mut b) = (self, other);
+                while b != 0 {
+                    let r = a % b;
+                    a = b;
+                    b = r;
+                }
+                a.abs()
+            }
+
+            fn lcm(self, other: Self) -> Self {
+                if self == 0 || other == 0 {
+                    0
+                } else {
+                    (self / self.gcd(other) * other).abs()
+                }
+            }
+
+            fn mod_pow(self, exp: u64, modulus: Self) -> Self {
+                let base = (self as i128).rem_euclid(modulus as i128) as u64;
+                mod_pow(base, exp, modulus as u64) as $t
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_int_ext_unsigned {
+    ($($t:ty),*) => {$(
+        impl IntExt for $t {
+            fn gcd(self, other: Self) -> Self {
+                let (mut a, mut b) = (self, other);
+                while b != 0 {
+                    let r = a % b;
+                    a = b;
+                    b = r;
+                }
+                a
+            }
+
+            fn lcm(self, other: Self) -> Self {
+                if self == 0 || other == 0 {
+                    0
+                } else {
+                    self / self.gcd(other) * other
+                }
+            }
+
+            fn mod_pow(self, exp: u64, modulus: Self) -> Self {
+                let base = (self as u128 % modulus as u128) as u64;
+                mod_pow(base, exp, modulus as u64) as $t
+            }
+        }
+    )*};
+}
+
+impl_int_ext_signed!(i8, i16, i32, i64, i128, isize);
+impl_int_ext_unsigned!(u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod test {
+    use super::{
+        crt, crt_many, discrete_log, ext_gcd, gcd, is_perfect_square, isqrt, lcm, mod_inv, mod_pow,
+        IntExt,
+    };
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(6, gcd(12, 18));
+        assert_eq!(6, gcd(-12, 18));
+        assert_eq!(5, gcd(0, 5));
+        assert_eq!(0, gcd(0, 0));
+        assert_eq!(36, lcm(12, 18));
+        assert_eq!(0, lcm(0, 3));
+    }
+
+    #[test]
+    fn test_ext_gcd() {
+        for &(a, b) in [(12, 18), (35, 15), (-4, 6), (7, 0), (0, 9), (240, -46)].iter() {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(gcd(a, b), g);
+            assert_eq!(g, a * x + b * y);
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(Some((8, 15)), crt(2, 3, 3, 5));
+        assert_eq!(Some((10, 12)), crt(2, 4, 4, 6));
+        assert_eq!(None, crt(1, 4, 2, 6));
+        assert_eq!(Some((4, 7)), crt(-3, 7, 4, 7));
+        assert_eq!(
+            Some((999_999_999_999, 1_000_000_007 * 999_999_937)),
+            crt(
+                999_999_999_999 % 1_000_000_007,
+                1_000_000_007,
+                999_999_999_999 % 999_999_937,
+                999_999_937
+            )
+        );
+    }
+
+    #[test]
+    fn test_crt_many() {
+        assert_eq!(Some((23, 105)), crt_many(&[(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Some((0, 1)), crt_many(&[]));
+        assert_eq!(None, crt_many(&[(1, 2), (0, 4), (1, 3)]));
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(24, mod_pow(2, 10, 1000));
+        assert_eq!(1, mod_pow(7, 0, 13));
+        assert_eq!(0, mod_pow(7, 0, 1));
+        assert_eq!(1, mod_pow(3, 1_000_000_006, 1_000_000_007));
+        assert_eq!(
+            (u64::MAX - 1) % (u64::MAX - 2),
+            mod_pow(u64::MAX - 1, 1, u64::MAX - 2)
+        );
+    }
+
+    #[test]
+    fn test_discrete_log() {
+        assert_eq!(Some(3), discrete_log(2, 8, 1000));
+        assert_eq!(Some(4), discrete_log(3, 13, 17));
+        assert_eq!(Some(0), discrete_log(5, 1, 7));
+        assert_eq!(None, discrete_log(2, 3, 4));
+        assert_eq!(None, discrete_log(2, 3, 7));
+        assert_eq!(Some(0), discrete_log(3, 5, 1));
+        assert_eq!(Some(1), discrete_log(0, 0, 1 << 63));
+        assert_eq!(Some(5), discrete_log(2, 32, 3 << 62));
+        assert_eq!(None, discrete_log(2, 3, 3 << 62));
+
+        for m in 1..40u64 {
+            for a in 0..m {
+                for b in 0..m {
+                    let expected = (0..2 * m).find(|&x| mod_pow(a, x, m) == b % m);
+                    assert_eq!(expected, discrete_log(a, b, m), "{} {} {}", a, b, m);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mod_inv() {
+        assert_eq!(Some(4), mod_inv(3, 11));
+        assert_eq!(Some(500_000_004), mod_inv(2, 1_000_000_007));
+        assert_eq!(Some(1), mod_inv(12, 11));
+        assert_eq!(None, mod_inv(4, 8));
+        assert_eq!(None, mod_inv(0, 7));
+        for a in 1..13 {
+            assert_eq!(1, a * mod_inv(a, 13).unwrap() % 13);
+        }
+    }
+
+    #[test]
+    fn test_mod_inv_large_modulus() {
+        for &m in &[(1u64 << 61) - 1, 18_446_744_073_709_551_557] {
+            for &a in &[2, 3, m - 1, m / 3, 1_234_567_890_123] {
+                let inv = mod_inv(a, m).unwrap();
+                assert!(inv < m);
+                assert_eq!(1, (a as u128 * inv as u128 % m as u128) as u64);
+            }
+        }
+        assert_eq!(None, mod_inv(6, u64::MAX - 1));
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0..10000u64 {
+            let x = isqrt(n);
+            assert!(x * x <= n && n < (x + 1) * (x + 1));
+            assert_eq!(x * x == n, is_perfect_square(n));
+        }
+        assert_eq!(u32::MAX as u64, isqrt(u64::MAX));
+        assert!(!is_perfect_square(u64::MAX));
+        for r in [u32::MAX as u64, 4_294_967_294, 3_037_000_499, 1_000_000_007].iter() {
+            let sq = r * r;
+            assert_eq!(*r, isqrt(sq));
+            assert_eq!(r - 1, isqrt(sq - 1));
+            assert!(is_perfect_square(sq));
+            assert!(!is_perfect_square(sq - 1));
+            if let Some(next) = sq.checked_add(1) {
+                assert_eq!(*r, isqrt(next));
+                assert!(!is_perfect_square(next));
+            }
+        }
+    }
+
+    #[test]
+    fn test_int_ext() {
+        for a in -30i64..30 {
+            for b in -30i64..30 {
+                assert_eq!(gcd(a, b), a.gcd(b));
+                assert_eq!(lcm(a, b), a.lcm(b));
+                if a >= 0 && b > 0 {
+                    assert_eq!(gcd(a, b) as u32, (a as u32).gcd(b as u32));
+                    assert_eq!(lcm(a, b) as u16, (a as u16).lcm(b as u16));
+                    for exp in 0..5 {
+                        assert_eq!(
+                            mod_pow(a as u64, exp, b as u64),
+                            (a as u64).mod_pow(exp, b as u64)
+                        );
+                    }
+                }
+            }
+        }
+        assert_eq!(6, (-4i32).mod_pow(3, 7));
+        assert_eq!(24usize, 2usize.mod_pow(10, 1000));
+        assert_eq!(1, 3u128.mod_pow(1_000_000_006, 1_000_000_007));
+        assert_eq!(6i128, 12i128.gcd(-18));
+        assert_eq!(36u16, 12u16.lcm(18));
+    }
+}
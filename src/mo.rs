@@ -0,0 +1,139 @@
+/// Offline range queries by Mo's algorithm. Queries are half-open `(l, r)` ranges over
+/// `0..n`, reordered so the window moves O((n + q) * sqrt(n)) times in total.
+#[derive(Clone, Debug)]
+pub struct Mo {
+    n: usize,
+    queries: Vec<(usize, usize)>,
+}
+
+impl Mo {
+    pub fn new(n: usize, queries: &[(usize, usize)]) -> Self {
+        for &(l, r) in queries {
+            assert!(l <= r && r <= n);
+        }
+        Self {
+            n,
+            queries: queries.to_vec(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.queries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
+
+    fn order(&self) -> Vec<usize> {
+        let q = self.queries.len().max(1);
+        let block = (self.n / ((q as f64).sqrt() as usize).max(1)).max(1);
+        let mut order = (0..self.queries.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| {
+            let (l, r) = self.queries[i];
+            let b = l / block;
+            (b, if b.is_multiple_of(2) { r } else { self.n - r })
+        });
+        order
+    }
+
+    /// Moves a window over `state`, calling `add`/`remove` with the index entering or leaving
+    /// it, and returns `answer` for each query in the order the queries were given.
+    pub fn run<S, T>(
+        &self,
+        state: &mut S,
+        mut add: impl FnMut(&mut S, usize),
+        mut remove: impl FnMut(&mut S, usize),
+        mut answer: impl FnMut(&S) -> T,
+    ) -> Vec<T> {
+        let mut answers = (0..self.queries.len()).map(|_| None).collect::<Vec<_>>();
+        let (mut left, mut right) = (0, 0);
+        for i in self.order() {
+            let (l, r) = self.queries[i];
+            while left > l {
+                left -= 1;
+                add(state, left);
+            }
+            while right < r {
+                add(state, right);
+                right += 1;
+            }
+            while left < l {
+                remove(state, left);
+                left += 1;
+            }
+            while right > r {
+                right -= 1;
+                remove(state, right);
+            }
+            answers[i] = Some(answer(state));
+        }
+        answers.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Mo;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::HashSet;
+
+    fn count_distinct(data: &[usize], queries: &[(usize, usize)]) -> Vec<usize> {
+        let max = data.iter().max().map_or(0, |&m| m + 1);
+        let mut state = (vec![0usize; max], 0usize);
+        Mo::new(data.len(), queries).run(
+            &mut state,
+            |(count, distinct), i| {
+                count[data[i]] += 1;
+                if count[data[i]] == 1 {
+                    *distinct += 1;
+                }
+            },
+            |(count, distinct), i| {
+                count[data[i]] -= 1;
+                if count[data[i]] == 0 {
+                    *distinct -= 1;
+                }
+            },
+            |&(_, distinct)| distinct,
+        )
+    }
+
+    #[test]
+    fn test_count_distinct() {
+        let data = [1, 2, 1, 3, 2, 2];
+        assert_eq!(
+            vec![3, 1, 0, 2, 3],
+            count_distinct(&data, &[(0, 6), (4, 6), (3, 3), (0, 2), (1, 4)])
+        );
+        assert_eq!(Vec::<usize>::new(), count_distinct(&data, &[]));
+        assert_eq!(vec![0], count_distinct(&[], &[(0, 0)]));
+    }
+
+    #[test]
+    fn test_count_distinct_random() {
+        let mut rng = SmallRng::seed_from_u64(888);
+        for _ in 0..20 {
+            let n = rng.gen_range(1, 60);
+            let data = (0..n).map(|_| rng.gen_range(0, 10)).collect::<Vec<usize>>();
+            let queries = (0..rng.gen_range(1, 80))
+                .map(|_| {
+                    let (a, b) = (rng.gen_range(0, n + 1), rng.gen_range(0, n + 1));
+                    (a.min(b), a.max(b))
+                })
+                .collect::<Vec<_>>();
+            let expected = queries
+                .iter()
+                .map(|&(l, r)| data[l..r].iter().collect::<HashSet<_>>().len())
+                .collect::<Vec<_>>();
+            assert_eq!(expected, count_distinct(&data, &queries));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_out_of_range() {
+        Mo::new(3, &[(1, 4)]);
+    }
+}
@@ -0,0 +1,75 @@
+// With the default `num` feature these are `num`'s own traits, so its implementations keep
+// working; without it, crate-local equivalents cover the primitive types.
+#[cfg(feature = "num")]
+pub use num::{Bounded, One, Zero};
+
+#[cfg(not(feature = "num"))]
+pub use self::fallback::{Bounded, One, Zero};
+
+#[cfg(not(feature = "num"))]
+mod fallback {
+    use std::ops::{Add, Mul};
+
+    pub trait Zero: Sized + Add<Output = Self> {
+        fn zero() -> Self;
+        fn is_zero(&self) -> bool;
+    }
+
+    pub trait One: Sized + Mul<Output = Self> {
+        fn one() -> Self;
+    }
+
+    pub trait Bounded {
+        fn min_value() -> Self;
+        fn max_value() -> Self;
+    }
+
+    macro_rules! impl_numeric {
+        ($zero:expr, $one:expr; $($t:ty),*) => {$(
+            impl Zero for $t {
+                fn zero() -> Self {
+                    $zero
+                }
+
+                fn is_zero(&self) -> bool {
+                    *self == $zero
+                }
+            }
+
+            impl One for $t {
+                fn one() -> Self {
+                    $one
+                }
+            }
+
+            impl Bounded for $t {
+                fn min_value() -> Self {
+                    <$t>::MIN
+                }
+
+                fn max_value() -> Self {
+                    <$t>::MAX
+                }
+            }
+        )*};
+    }
+
+    impl_numeric!(0, 1; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+    impl_numeric!(0.0, 1.0; f32, f64);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Bounded, One, Zero};
+
+    #[test]
+    fn test_numeric() {
+        assert_eq!(0, i64::zero());
+        assert_eq!(1, u8::one());
+        assert_eq!(0.0, f64::zero());
+        assert!(0usize.is_zero());
+        assert!(!1i32.is_zero());
+        assert_eq!(i32::MIN, <i32 as Bounded>::min_value());
+        assert_eq!(u64::MAX, <u64 as Bounded>::max_value());
+    }
+}
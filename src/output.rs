@@ -1,17 +1,37 @@
 use std::fmt::{Display, Formatter};
-use std::io::{stdout, Stdout, Write};
+use std::io::{stdout, BufWriter, Stdout, Write};
 
 #[allow(non_camel_case_types)]
 pub enum OutputType {
     iSize(isize),
     Int32(i32),
     Int64(i64),
+    Int128(i128),
     uSize(usize),
     uInt32(u32),
     uInt64(u64),
+    uInt128(u128),
     Bool(bool),
+    Char(char),
     String(String),
     Vec(Vec<OutputType>),
+    Line(Vec<OutputType>),
+    Grid(Vec<Vec<OutputType>>),
+    Optional(Option<Box<OutputType>>),
+}
+
+impl OutputType {
+    pub fn line<T: Into<OutputType>>(v: Vec<T>) -> Self {
+        Self::Line(v.into_iter().map(|e| e.into()).collect())
+    }
+
+    pub fn grid<T: Into<OutputType>>(v: Vec<Vec<T>>) -> Self {
+        Self::Grid(
+            v.into_iter()
+                .map(|row| row.into_iter().map(|e| e.into()).collect())
+                .collect(),
+        )
+    }
 }
 
 impl From<isize> for OutputType {
@@ -32,6 +52,12 @@ impl From<i64> for OutputType {
     }
 }
 
+impl From<i128> for OutputType {
+    fn from(v: i128) -> Self {
+        Self::Int128(v)
+    }
+}
+
 impl From<usize> for OutputType {
     fn from(v: usize) -> Self {
         Self::uSize(v)
@@ -50,64 +76,211 @@ impl From<u64> for OutputType {
     }
 }
 
+impl From<u128> for OutputType {
+    fn from(v: u128) -> Self {
+        Self::uInt128(v)
+    }
+}
+
 impl From<bool> for OutputType {
     fn from(v: bool) -> Self {
         Self::Bool(v)
     }
 }
 
+impl From<char> for OutputType {
+    fn from(v: char) -> Self {
+        Self::Char(v)
+    }
+}
+
 impl From<String> for OutputType {
     fn from(v: String) -> Self {
         Self::String(v)
     }
 }
 
+impl From<&str> for OutputType {
+    fn from(v: &str) -> Self {
+        Self::String(v.to_string())
+    }
+}
+
+impl<A: Into<OutputType>, B: Into<OutputType>> From<(A, B)> for OutputType {
+    fn from((a, b): (A, B)) -> Self {
+        Self::Line(vec![a.into(), b.into()])
+    }
+}
+
+impl<A, B, C> From<(A, B, C)> for OutputType
+where
+    A: Into<OutputType>,
+    B: Into<OutputType>,
+    C: Into<OutputType>,
+{
+    fn from((a, b, c): (A, B, C)) -> Self {
+        Self::Line(vec![a.into(), b.into(), c.into()])
+    }
+}
+
+impl<T: Into<OutputType>> From<Option<T>> for OutputType {
+    fn from(v: Option<T>) -> Self {
+        Self::Optional(v.map(|e| Box::new(e.into())))
+    }
+}
+
 impl<T: Into<OutputType>> From<Vec<T>> for OutputType {
     fn from(v: Vec<T>) -> Self {
         Self::Vec(v.into_iter().map(|e| e.into()).collect())
     }
 }
 
-impl Display for OutputType {
+struct Labels {
+    yes: String,
+    no: String,
+    none: String,
+}
+
+impl Default for Labels {
+    fn default() -> Self {
+        Self {
+            yes: "Yes".to_string(),
+            no: "No".to_string(),
+            none: "-1".to_string(),
+        }
+    }
+}
+
+struct Labeled<'a>(&'a OutputType, &'a Labels);
+
+impl<'a> Labeled<'a> {
+    fn join(&self, f: &mut Formatter, v: &[OutputType], separator: &str) -> std::fmt::Result {
+        for (i, e) in v.iter().enumerate() {
+            if i > 0 {
+                write!(f, "{}", separator)?;
+            }
+            write!(f, "{}", Labeled(e, self.1))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Display for Labeled<'a> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        match self {
-            Self::iSize(value) => write!(f, "{}", *value),
-            Self::Int32(value) => write!(f, "{}", *value),
-            Self::Int64(value) => write!(f, "{}", *value),
-            Self::uSize(value) => write!(f, "{}", *value),
-            Self::uInt32(value) => write!(f, "{}", *value),
-            Self::uInt64(value) => write!(f, "{}", *value),
-            Self::String(value) => write!(f, "{}", *value),
-            Self::Bool(value) => {
+        match self.0 {
+            OutputType::iSize(value) => write!(f, "{}", *value),
+            OutputType::Int32(value) => write!(f, "{}", *value),
+            OutputType::Int64(value) => write!(f, "{}", *value),
+            OutputType::Int128(value) => write!(f, "{}", *value),
+            OutputType::uSize(value) => write!(f, "{}", *value),
+            OutputType::uInt32(value) => write!(f, "{}", *value),
+            OutputType::uInt64(value) => write!(f, "{}", *value),
+            OutputType::uInt128(value) => write!(f, "{}", *value),
+            OutputType::Char(value) => write!(f, "{}", *value),
+            OutputType::String(value) => write!(f, "{}", *value),
+            OutputType::Bool(value) => {
                 if *value {
-                    write!(f, "Yes")
+                    write!(f, "{}", self.1.yes)
                 } else {
-                    write!(f, "No")
+                    write!(f, "{}", self.1.no)
                 }
             }
-            Self::Vec(v) => {
-                write!(
-                    f,
-                    "{}",
-                    v.into_iter()
-                        .map(|e| format!("{}", e))
-                        .collect::<Vec<_>>()
-                        .join("\n")
-                )
+            OutputType::Vec(v) => self.join(f, v, "\n"),
+            OutputType::Line(v) => self.join(f, v, " "),
+            OutputType::Grid(rows) => {
+                for (i, row) in rows.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    self.join(f, row, " ")?;
+                }
+                Ok(())
             }
+            OutputType::Optional(Some(v)) => write!(f, "{}", Labeled(v, self.1)),
+            OutputType::Optional(None) => write!(f, "{}", self.1.none),
         }
     }
 }
 
-pub struct Output<T: Write>(T);
+impl Display for OutputType {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", Labeled(self, &Labels::default()))
+    }
+}
+
+pub struct Output<T: Write> {
+    destination: BufWriter<T>,
+    labels: Labels,
+    auto_flush: bool,
+}
 
 impl<T: Write> Output<T> {
     pub fn new(destination: T) -> Self {
-        Self(destination)
+        Self {
+            destination: BufWriter::new(destination),
+            labels: Labels::default(),
+            auto_flush: false,
+        }
+    }
+
+    pub fn interactive(destination: T) -> Self {
+        let mut output = Self::new(destination);
+        output.auto_flush = true;
+        output
+    }
+
+    pub fn set_bool_labels(&mut self, yes: &str, no: &str) {
+        self.labels.yes = yes.to_string();
+        self.labels.no = no.to_string();
+    }
+
+    pub fn set_none_label(&mut self, none: &str) {
+        self.labels.none = none.to_string();
     }
 
     pub fn write(&mut self, result: OutputType) {
-        self.0.write_fmt(format_args!("{}\n", result)).unwrap();
+        self.write_with(result, "\n");
+    }
+
+    pub fn write_no_newline(&mut self, result: OutputType) {
+        self.write_with(result, "");
+    }
+
+    /// Writes each result on its own line; an interactive `Output` flushes once at the end.
+    pub fn write_all<I: IntoIterator<Item = OutputType>>(&mut self, results: I) {
+        for result in results {
+            self.put(&result, "\n");
+        }
+        if self.auto_flush {
+            self.flush();
+        }
+    }
+
+    fn write_with(&mut self, result: OutputType, terminator: &str) {
+        self.put(&result, terminator);
+        if self.auto_flush {
+            self.flush();
+        }
+    }
+
+    fn put(&mut self, result: &OutputType, terminator: &str) {
+        self.destination
+            .write_fmt(format_args!(
+                "{}{}",
+                Labeled(result, &self.labels),
+                terminator
+            ))
+            .unwrap();
+    }
+
+    pub fn flush(&mut self) {
+        self.destination.flush().unwrap();
+    }
+}
+
+impl<T: Write> Drop for Output<T> {
+    fn drop(&mut self) {
+        let _ = self.destination.flush();
     }
 }
 
@@ -117,6 +290,16 @@ impl Default for Output<Stdout> {
     }
 }
 
+/// Writes the given values on one line, separated by spaces.
+#[macro_export]
+macro_rules! print_line {
+    ($output:expr $(, $v:expr)* $(,)?) => {
+        $output.write($crate::output::OutputType::Line(vec![
+            $($crate::output::OutputType::from($v)),*
+        ]))
+    };
+}
+
 #[cfg(test)]
 mod test {
     mod output_type {
@@ -217,5 +400,216 @@ mod test {
                 format!("{}", OutputType::from(u64::MIN))
             );
         }
+
+        #[test]
+        fn test_i128() {
+            assert_eq!("0", format!("{}", OutputType::from(0i128)));
+            assert_eq!("-10", format!("{}", OutputType::from(-10i128)));
+            assert_eq!(
+                "-170141183460469231731687303715884105728",
+                format!("{}", OutputType::from(i128::MIN))
+            );
+            assert_eq!(
+                format!("{}", i128::MAX),
+                format!("{}", OutputType::from(i128::MAX))
+            );
+        }
+
+        #[test]
+        fn test_u128() {
+            assert_eq!("0", format!("{}", OutputType::from(0u128)));
+            assert_eq!("500", format!("{}", OutputType::from(500u128)));
+            assert_eq!(
+                format!("{}", u128::MAX),
+                format!("{}", OutputType::from(u128::MAX))
+            );
+        }
+
+        #[test]
+        fn test_char() {
+            assert_eq!("a", format!("{}", OutputType::from('a')));
+            assert_eq!("#", format!("{}", OutputType::from('#')));
+            assert_eq!("# .", format!("{}", OutputType::line(vec!['#', '.'])));
+        }
+
+        #[test]
+        fn test_vec() {
+            assert_eq!("", format!("{}", OutputType::from(Vec::<i32>::new())));
+            assert_eq!("1\n2\n3", format!("{}", OutputType::from(vec![1, 2, 3])));
+        }
+
+        #[test]
+        fn test_line() {
+            assert_eq!("", format!("{}", OutputType::line(Vec::<i32>::new())));
+            assert_eq!("1 2 3", format!("{}", OutputType::line(vec![1, 2, 3])));
+            assert_eq!(
+                "a b",
+                format!(
+                    "{}",
+                    OutputType::line(vec!["a".to_string(), "b".to_string()])
+                )
+            );
+        }
+
+        #[test]
+        fn test_matrix() {
+            let matrix = vec![vec![1, 2, 3], vec![4, 5, 6]];
+            assert_eq!(
+                "1 2 3\n4 5 6",
+                format!(
+                    "{}",
+                    OutputType::Vec(matrix.into_iter().map(OutputType::line).collect())
+                )
+            );
+        }
+
+        #[test]
+        fn test_tuple() {
+            assert_eq!("3 4", format!("{}", OutputType::from((3i64, 4i64))));
+            assert_eq!(
+                "1 x Yes",
+                format!("{}", OutputType::from((1usize, 'x', true)))
+            );
+            assert_eq!(
+                "1 2\n3 4",
+                format!("{}", OutputType::from(vec![(1, 2), (3, 4)]))
+            );
+        }
+
+        #[test]
+        fn test_option() {
+            assert_eq!("5", format!("{}", OutputType::from(Some(5))));
+            assert_eq!("-1", format!("{}", OutputType::from(None::<i32>)));
+            assert_eq!(
+                "1 -1 3",
+                format!("{}", OutputType::line(vec![Some(1), None, Some(3)]))
+            );
+            assert_eq!("Yes", format!("{}", OutputType::from(Some(true))));
+        }
+
+        #[test]
+        fn test_grid() {
+            assert_eq!(
+                "1 2\n3 4",
+                format!("{}", OutputType::grid(vec![vec![1, 2], vec![3, 4]]))
+            );
+            assert_eq!("", format!("{}", OutputType::grid(Vec::<Vec<i32>>::new())));
+            assert_eq!(
+                "# .\n. #",
+                format!("{}", OutputType::grid(vec![vec!['#', '.'], vec!['.', '#']]))
+            );
+            assert_eq!(
+                "Yes No",
+                format!("{}", OutputType::grid(vec![vec![true, false]]))
+            );
+        }
+    }
+
+    mod output {
+        use super::super::*;
+
+        #[test]
+        fn test_write() {
+            let mut buf = vec![];
+            let mut output = Output::new(&mut buf);
+            output.write(OutputType::from(1));
+            output.write(OutputType::from(true));
+            output.write(OutputType::from(false));
+            drop(output);
+            assert_eq!(b"1\nYes\nNo\n".to_vec(), buf);
+        }
+
+        #[test]
+        fn test_set_bool_labels() {
+            let mut buf = vec![];
+            let mut output = Output::new(&mut buf);
+            output.set_bool_labels("YES", "NO");
+            output.write(OutputType::from(true));
+            output.write(OutputType::from(vec![false, true]));
+            output.set_bool_labels("Possible", "Impossible");
+            output.write(OutputType::line(vec![true, false]));
+            drop(output);
+            assert_eq!(b"YES\nNO\nYES\nPossible Impossible\n".to_vec(), buf);
+        }
+
+        #[test]
+        fn test_write_all() {
+            let mut buf = vec![];
+            let mut output = Output::new(&mut buf);
+            output.write_all(vec![1, 2, 3].into_iter().map(OutputType::from));
+            output.write_all(vec![]);
+            output.set_bool_labels("YES", "NO");
+            output.write_all(vec![OutputType::from(true), OutputType::line(vec![4, 5])]);
+            drop(output);
+            assert_eq!(b"1\n2\n3\nYES\n4 5\n".to_vec(), buf);
+
+            let mut buf = vec![];
+            let mut output = Output::interactive(&mut buf);
+            output.write_all(vec![OutputType::from("a"), OutputType::from('b')]);
+            std::mem::forget(output);
+            assert_eq!(b"a\nb\n".to_vec(), buf);
+        }
+
+        #[test]
+        fn test_flush() {
+            let mut buf = vec![];
+            let mut output = Output::new(&mut buf);
+            output.write(OutputType::from(1));
+            output.flush();
+            output.write(OutputType::from(2));
+            output.flush();
+            drop(output);
+            assert_eq!(b"1\n2\n".to_vec(), buf);
+        }
+
+        #[test]
+        fn test_drop() {
+            let mut buf = vec![];
+            {
+                let mut output = Output::new(&mut buf);
+                for i in 0..100000 {
+                    output.write(OutputType::from(i));
+                }
+            }
+            let expected = (0..100000).map(|i| format!("{}\n", i)).collect::<String>();
+            assert_eq!(expected.into_bytes(), buf);
+        }
+
+        #[test]
+        fn test_print_line() {
+            let mut buf = vec![];
+            let mut output = Output::new(&mut buf);
+            crate::print_line!(output, 1, 2, "x");
+            crate::print_line!(output, true, 'c', -5i64,);
+            crate::print_line!(output);
+            drop(output);
+            assert_eq!(b"1 2 x\nYes c -5\n\n".to_vec(), buf);
+        }
+
+        #[test]
+        fn test_write_no_newline() {
+            let mut buf = vec![];
+            let mut output = Output::new(&mut buf);
+            output.write_no_newline(OutputType::from("? "));
+            output.write_no_newline(OutputType::from(3));
+            output.write(OutputType::from(" 4"));
+            output.write_no_newline(OutputType::line(vec![true, false]));
+            drop(output);
+            assert_eq!(b"? 3 4\nYes No".to_vec(), buf);
+        }
+
+        #[test]
+        fn test_set_none_label() {
+            let mut buf = vec![];
+            let mut output = Output::new(&mut buf);
+            output.write(OutputType::from(None::<u64>));
+            output.set_none_label("IMPOSSIBLE");
+            output.set_bool_labels("YES", "NO");
+            output.write(OutputType::from(None::<u64>));
+            output.write(OutputType::from(Some(7u64)));
+            output.write(OutputType::from(Some(false)));
+            drop(output);
+            assert_eq!(b"-1\nIMPOSSIBLE\n7\nNO\n".to_vec(), buf);
+        }
     }
 }
@@ -0,0 +1,81 @@
+fn step_permutation<T>(slice: &mut [T], ascending: impl Fn(&T, &T) -> bool) -> bool {
+    let pivot = match (1..slice.len())
+        .rev()
+        .find(|&i| ascending(&slice[i - 1], &slice[i]))
+    {
+        Some(i) => i - 1,
+        None => {
+            slice.reverse();
+            return false;
+        }
+    };
+    let swap = (pivot + 1..slice.len())
+        .rev()
+        .find(|&i| ascending(&slice[pivot], &slice[i]))
+        .unwrap();
+    slice.swap(pivot, swap);
+    slice[pivot + 1..].reverse();
+    true
+}
+
+pub fn next_permutation<T: Ord>(slice: &mut [T]) -> bool {
+    step_permutation(slice, |a, b| a < b)
+}
+
+pub fn prev_permutation<T: Ord>(slice: &mut [T]) -> bool {
+    step_permutation(slice, |a, b| a > b)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{next_permutation, prev_permutation};
+
+    #[test]
+    fn test_next_permutation() {
+        let mut v = vec![1, 2, 3];
+        let mut seen = vec![v.clone()];
+        while next_permutation(&mut v) {
+            seen.push(v.clone());
+        }
+        assert_eq!(
+            vec![
+                vec![1, 2, 3],
+                vec![1, 3, 2],
+                vec![2, 1, 3],
+                vec![2, 3, 1],
+                vec![3, 1, 2],
+                vec![3, 2, 1],
+            ],
+            seen
+        );
+        assert_eq!(vec![1, 2, 3], v);
+
+        let mut v = vec![1, 1, 2];
+        let mut count = 1;
+        while next_permutation(&mut v) {
+            count += 1;
+        }
+        assert_eq!(3, count);
+
+        let mut empty: [i32; 0] = [];
+        assert!(!next_permutation(&mut empty));
+    }
+
+    #[test]
+    fn test_prev_permutation() {
+        let mut v = vec![3, 2, 1];
+        let mut seen = vec![v.clone()];
+        while prev_permutation(&mut v) {
+            seen.push(v.clone());
+        }
+        assert_eq!(6, seen.len());
+        assert!(seen.windows(2).all(|w| w[0] > w[1]));
+        assert_eq!(vec![3, 2, 1], v);
+
+        let mut v = vec![2, 1, 3];
+        assert!(prev_permutation(&mut v));
+        assert_eq!(vec![1, 3, 2], v);
+        assert!(next_permutation(&mut v));
+        assert_eq!(vec![2, 1, 3], v);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::numeric::Zero;
+use crate::range::bounds;
+use std::ops::{Add, RangeBounds, Sub};
+
+/// `sums[i]` is the sum of `data[..i]`, so `sums[r] - sums[l]` is the sum of `data[l..r]`.
+pub fn prefix_sums<T: Copy + Add<Output = T> + Zero>(data: &[T]) -> Vec<T> {
+    let mut sums = Vec::with_capacity(data.len() + 1);
+    sums.push(T::zero());
+    for &x in data {
+        let last = *sums.last().unwrap();
+        sums.push(last + x);
+    }
+    sums
+}
+
+/// Rectangle sums over an immutable `h` x `w` matrix in O(1) after O(hw) preprocessing.
+#[derive(Clone, Debug)]
+pub struct PrefixSum2D<T> {
+    h: usize,
+    w: usize,
+    sums: Vec<Vec<T>>,
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Zero> PrefixSum2D<T> {
+    pub fn new(matrix: &[Vec<T>]) -> Self {
+        let h = matrix.len();
+        let w = matrix.first().map_or(0, |row| row.len());
+        let mut sums = vec![vec![T::zero(); w + 1]; h + 1];
+        for (i, row) in matrix.iter().enumerate() {
+            assert_eq!(w, row.len());
+            for (j, &x) in row.iter().enumerate() {
+                sums[i + 1][j + 1] = sums[i][j + 1] + sums[i + 1][j] - sums[i][j] + x;
+            }
+        }
+        Self { h, w, sums }
+    }
+
+    pub fn height(&self) -> usize {
+        self.h
+    }
+
+    pub fn width(&self) -> usize {
+        self.w
+    }
+
+    /// Sum over the rows in `rows` and the columns in `cols`; zero when either is empty.
+    pub fn sum(&self, rows: impl RangeBounds<usize>, cols: impl RangeBounds<usize>) -> T {
+        let (top, bottom) = bounds(rows, self.h);
+        let (left, right) = bounds(cols, self.w);
+        self.sums[bottom][right] + self.sums[top][left]
+            - self.sums[top][right]
+            - self.sums[bottom][left]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{prefix_sums, PrefixSum2D};
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_prefix_sums() {
+        assert_eq!(vec![0], prefix_sums::<i64>(&[]));
+        assert_eq!(vec![0, 3, 2, 6, 6], prefix_sums(&[3, -1, 4, 0]));
+        assert_eq!(vec![0.0, 0.5, 2.0], prefix_sums(&[0.5, 1.5]));
+
+        let mut rng = SmallRng::seed_from_u64(885);
+        let data = (0..30)
+            .map(|_| rng.gen_range(-50, 50))
+            .collect::<Vec<i64>>();
+        let sums = prefix_sums(&data);
+        for l in 0..=data.len() {
+            for r in l..=data.len() {
+                assert_eq!(data[l..r].iter().sum::<i64>(), sums[r] - sums[l]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_prefix_sum_2d() {
+        let mut rng = SmallRng::seed_from_u64(885);
+        let (h, w) = (5, 7);
+        let matrix = (0..h)
+            .map(|_| (0..w).map(|_| rng.gen_range(-9, 10)).collect())
+            .collect::<Vec<Vec<i64>>>();
+        let sums = PrefixSum2D::new(&matrix);
+        assert_eq!((h, w), (sums.height(), sums.width()));
+        for top in 0..=h {
+            for bottom in top..=h {
+                for left in 0..=w {
+                    for right in left..=w {
+                        let expected = matrix[top..bottom]
+                            .iter()
+                            .map(|row| row[left..right].iter().sum::<i64>())
+                            .sum::<i64>();
+                        assert_eq!(expected, sums.sum(top..bottom, left..right));
+                    }
+                }
+            }
+        }
+        let total = matrix.iter().flatten().sum::<i64>();
+        assert_eq!(total, sums.sum(.., ..));
+        assert_eq!(matrix[2][3], sums.sum(2..=2, 3..=3));
+        assert_eq!(0, sums.sum(3..3, ..));
+        assert_eq!(0, sums.sum(.., 4..4));
+    }
+
+    #[test]
+    fn test_prefix_sum_2d_empty() {
+        let sums = PrefixSum2D::<i64>::new(&[]);
+        assert_eq!(0, sums.sum(.., ..));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_prefix_sum_2d_out_of_bounds() {
+        PrefixSum2D::new(&[vec![1, 2], vec![3, 4]]).sum(0..3, ..);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::math::{isqrt, UInt};
 use std::cmp::min;
 
 #[derive(PartialEq, Eq, Debug)]
@@ -22,8 +23,15 @@ impl Iterator for SieveOfEratosthenes {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.sieve.len() - self.index;
+        (remaining, Some(remaining))
+    }
 }
 
+impl ExactSizeIterator for SieveOfEratosthenes {}
+
 pub fn sieve_of_eratosthenes(len: usize) -> SieveOfEratosthenes {
     let mut ret = SieveOfEratosthenes {
         sieve: vec![true; len],
@@ -35,26 +43,133 @@ pub fn sieve_of_eratosthenes(len: usize) -> SieveOfEratosthenes {
     ret
 }
 
-pub fn primes(n: impl Into<usize>) -> Vec<usize> {
-    sieve_of_eratosthenes(n.into())
+pub fn prime_iter(len: usize) -> impl Iterator<Item = usize> {
+    sieve_of_eratosthenes(len)
         .enumerate()
         .filter(|&(_, p)| p)
         .map(|(i, _)| i)
-        .collect::<Vec<_>>()
 }
 
-pub fn divisors(n: impl Into<usize>) -> Vec<usize> {
-    let n = n.into();
-    (1..=n)
-        .take_while(|i| i * i <= n)
-        .filter(|&i| n % i == 0)
-        .flat_map(|i| if i * i == n { vec![i] } else { vec![i, n / i] })
+/// Primes below `n`. Panics if `n` does not fit in `usize`.
+pub fn primes<T: UInt>(n: T) -> Vec<T> {
+    let len = n.as_usize();
+    assert!(T::from_usize(len) == n, "{:?} does not fit in usize", n);
+    prime_iter(len).map(T::from_usize).collect::<Vec<_>>()
+}
+
+pub fn is_prime(n: u64) -> bool {
+    n >= 2
+        && (2..)
+            .take_while(|&i| i <= n / i)
+            .all(|i| !n.is_multiple_of(i))
+}
+
+/// Clears the multiples of `p` from `p * p` on in `sieve`, which covers `lo..lo + sieve.len()`.
+fn cross_off(sieve: &mut [bool], lo: u64, p: u64) {
+    let first = match lo.div_ceil(p).checked_mul(p) {
+        Some(first) => first.max(p * p),
+        None => return,
+    };
+    let hi = lo + sieve.len() as u64;
+    for i in (first..hi).step_by(p as usize) {
+        sieve[(i - lo) as usize] = false;
+    }
+}
+
+/// Primes in `lo..hi`. Memory is O(hi - lo + 2^16): the base primes up to `sqrt(hi)` are
+/// themselves sieved block by block instead of being stored.
+pub fn segmented_sieve(lo: u64, hi: u64) -> Vec<u64> {
+    let lo = lo.max(2);
+    if lo >= hi {
+        return vec![];
+    }
+    let limit = isqrt(hi - 1);
+    let small = prime_iter(isqrt(limit) as usize + 1)
+        .map(|p| p as u64)
+        .collect::<Vec<_>>();
+
+    let mut sieve = vec![true; (hi - lo) as usize];
+    let block_size = 1 << 16;
+    let mut base = 2;
+    while base <= limit {
+        let end = min(base + block_size, limit + 1);
+        let mut block = vec![true; (end - base) as usize];
+        for &q in &small {
+            cross_off(&mut block, base, q);
+        }
+        for (i, _) in block.into_iter().enumerate().filter(|&(_, p)| p) {
+            cross_off(&mut sieve, lo, base + i as u64);
+        }
+        base = end;
+    }
+    sieve
+        .into_iter()
+        .enumerate()
+        .filter(|&(_, p)| p)
+        .map(|(i, _)| lo + i as u64)
         .collect::<Vec<_>>()
 }
 
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    let mut n = n;
+    let mut ret = vec![];
+    let mut p = 2;
+    while p <= n / p {
+        let mut e = 0;
+        while n.is_multiple_of(p) {
+            n /= p;
+            e += 1;
+        }
+        if e > 0 {
+            ret.push((p, e));
+        }
+        p += 1;
+    }
+    if n > 1 {
+        ret.push((n, 1));
+    }
+    ret
+}
+
+/// Panics if `n == 0`, which has infinitely many divisors.
+pub fn divisor_count(n: u64) -> u64 {
+    assert!(n > 0, "divisor_count(0) is undefined");
+    factorize(n)
+        .into_iter()
+        .map(|(_, e)| e as u64 + 1)
+        .product()
+}
+
+/// Panics if `n == 0`, which has infinitely many divisors.
+pub fn divisor_sum(n: u64) -> u64 {
+    assert!(n > 0, "divisor_sum(0) is undefined");
+    factorize(n)
+        .into_iter()
+        .map(|(p, e)| (0..=e).map(|i| p.pow(i)).sum::<u64>())
+        .product()
+}
+
+pub fn divisors<T: UInt>(n: T) -> Vec<T> {
+    let mut ret = vec![];
+    let mut i = T::ONE;
+    while i <= n / i {
+        if n % i == T::ZERO {
+            ret.push(i);
+            if i != n / i {
+                ret.push(n / i);
+            }
+        }
+        i = i + T::ONE;
+    }
+    ret
+}
+
 #[cfg(test)]
 mod test {
-    use super::{primes, sieve_of_eratosthenes};
+    use super::{
+        divisor_count, divisor_sum, divisors, factorize, is_prime, prime_iter, primes,
+        segmented_sieve, sieve_of_eratosthenes,
+    };
 
     mod sieve_of_eratosthenes {
         #[test]
@@ -86,6 +201,23 @@ mod test {
                 ]
             );
         }
+
+        #[test]
+        fn test_len() {
+            use super::super::sieve_of_eratosthenes;
+
+            let mut sieve = sieve_of_eratosthenes(10);
+            assert_eq!((10, Some(10)), sieve.size_hint());
+            assert_eq!(10, sieve.len());
+            sieve.next();
+            sieve.next();
+            sieve.next();
+            assert_eq!((7, Some(7)), sieve.size_hint());
+            assert_eq!(7, sieve.len());
+            assert_eq!(7, sieve.by_ref().count());
+            assert_eq!(0, sieve.len());
+            assert_eq!(0, sieve_of_eratosthenes(0).len());
+        }
     }
 
     #[test]
@@ -116,5 +248,173 @@ mod test {
     fn test_primes() {
         assert_eq!(vec![2, 3, 5, 7], primes(10usize));
         assert_eq!(vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29], primes(30usize));
+
+        let expected = primes(100u64);
+        assert_eq!(25, expected.len());
+        assert_eq!(
+            expected,
+            primes(100u32)
+                .into_iter()
+                .map(u64::from)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            expected,
+            primes(100u8).into_iter().map(u64::from).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            expected,
+            primes(100usize)
+                .into_iter()
+                .map(|p| p as u64)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Vec::<u32>::new(), primes(2u32));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in usize")]
+    fn test_primes_too_large() {
+        primes(u128::MAX);
+    }
+
+    #[test]
+    fn test_prime_iter() {
+        assert_eq!(primes(30usize), prime_iter(30).collect::<Vec<_>>());
+        assert_eq!(Vec::<usize>::new(), prime_iter(2).collect::<Vec<_>>());
+        assert_eq!(
+            vec![2, 3, 5],
+            prime_iter(1000000).take(3).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_is_prime() {
+        assert_eq!(
+            primes(100usize),
+            (0..100u64)
+                .filter(|&n| is_prime(n))
+                .map(|n| n as usize)
+                .collect::<Vec<_>>()
+        );
+        assert!(is_prime(1_000_000_007));
+        assert!(!is_prime(1_000_000_007 * 3));
+        assert!(is_prime(4_294_967_311));
+        assert!(!is_prime(4_294_967_311 * 3));
+    }
+
+    #[test]
+    #[ignore]
+    fn test_is_prime_near_u64_max() {
+        assert!(is_prime(18_446_744_073_709_551_557));
+        assert!(!is_prime(u64::MAX));
+    }
+
+    #[test]
+    fn test_segmented_sieve() {
+        for &(lo, hi) in &[
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (0, 100),
+            (1, 50),
+            (90, 200),
+            (97, 98),
+        ] {
+            assert_eq!(
+                (lo..hi).filter(|&n| is_prime(n)).collect::<Vec<_>>(),
+                segmented_sieve(lo, hi)
+            );
+        }
+        assert_eq!(Vec::<u64>::new(), segmented_sieve(10, 5));
+
+        let lo = 1_000_000_000_000;
+        let hi = lo + 1000;
+        assert_eq!(
+            (lo..hi).filter(|&n| is_prime(n)).collect::<Vec<_>>(),
+            segmented_sieve(lo, hi)
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_segmented_sieve_near_u64_max() {
+        assert_eq!(
+            vec![
+                18_446_744_073_709_551_427,
+                18_446_744_073_709_551_437,
+                18_446_744_073_709_551_521,
+                18_446_744_073_709_551_533,
+                18_446_744_073_709_551_557,
+            ],
+            segmented_sieve(u64::MAX - 200, u64::MAX)
+        );
+        assert_eq!(Vec::<u64>::new(), segmented_sieve(u64::MAX - 10, u64::MAX));
+    }
+
+    #[test]
+    fn test_factorize() {
+        assert_eq!(Vec::<(u64, u32)>::new(), factorize(1));
+        assert_eq!(vec![(2, 1)], factorize(2));
+        assert_eq!(vec![(2, 2), (3, 1), (5, 1)], factorize(60));
+        assert_eq!(vec![(1_000_000_007, 1)], factorize(1_000_000_007));
+        assert_eq!(vec![(2, 10)], factorize(1024));
+        assert_eq!(
+            vec![(2, 1), (4_294_967_311, 1)],
+            factorize(2 * 4_294_967_311)
+        );
+        assert_eq!(4, divisor_count(2 * 4_294_967_311));
+        assert_eq!(3 * 4_294_967_312, divisor_sum(2 * 4_294_967_311));
+    }
+
+    #[test]
+    fn test_divisor_count() {
+        for n in 1..3000u64 {
+            assert_eq!(divisors(n as usize).len() as u64, divisor_count(n));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "divisor_count(0) is undefined")]
+    fn test_divisor_count_zero() {
+        divisor_count(0);
+    }
+
+    #[test]
+    fn test_divisors() {
+        assert_eq!(Vec::<u64>::new(), divisors(0u64));
+        assert_eq!(vec![1], divisors(1u8));
+        let mut d = divisors(36usize);
+        d.sort();
+        assert_eq!(vec![1, 2, 3, 4, 6, 9, 12, 18, 36], d);
+
+        assert_eq!(32, divisors(u32::MAX).len());
+        assert_eq!(16, divisors(u16::MAX).len());
+        assert_eq!(u8::MAX, *divisors(u8::MAX).iter().max().unwrap());
+
+        let n = 10_000_000_000u64;
+        let d = divisors(n);
+        assert_eq!(divisor_count(n), d.len() as u64);
+        assert!(d.iter().all(|&i| n.is_multiple_of(i)));
+        assert!(d.contains(&n));
+
+        assert_eq!(41, divisors(1u128 << 40).len());
+    }
+
+    #[test]
+    fn test_divisor_sum() {
+        for n in 1..3000u64 {
+            assert_eq!(
+                divisors(n as usize).into_iter().sum::<usize>() as u64,
+                divisor_sum(n)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "divisor_sum(0) is undefined")]
+    fn test_divisor_sum_zero() {
+        divisor_sum(0);
     }
 }
@@ -0,0 +1,65 @@
+use std::ops::RangeBounds;
+
+/// Resolves `range` against `0..len` into a half-open `(begin, end)` without validating it.
+/// `None` when an inclusive end or exclusive start of `usize::MAX` cannot be converted.
+pub(crate) fn try_bounds(range: impl RangeBounds<usize>, len: usize) -> Option<(usize, usize)> {
+    use std::ops::Bound::*;
+
+    let begin = match range.start_bound() {
+        Unbounded => 0,
+        Included(&b) => b,
+        Excluded(&b) => b.checked_add(1)?,
+    };
+    let end = match range.end_bound() {
+        Unbounded => len,
+        Included(&e) => e.checked_add(1)?,
+        Excluded(&e) => e,
+    };
+    Some((begin, end))
+}
+
+/// Like `try_bounds`, but panics unless the range lies within `0..len` and is not inverted.
+pub(crate) fn bounds(range: impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    let (begin, end) = try_bounds(range, len).expect("range bound overflows usize");
+    assert!(begin <= end && end <= len);
+    (begin, end)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bounds, try_bounds};
+    use std::ops::Bound;
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_try_bounds() {
+        assert_eq!(Some((0, 5)), try_bounds(.., 5));
+        assert_eq!(Some((2, 4)), try_bounds(2..=3, 5));
+        assert_eq!(Some((3, 9)), try_bounds(3..9, 5));
+        assert_eq!(Some((4, 1)), try_bounds(4..1, 5));
+        assert_eq!(None, try_bounds(..=usize::MAX, 5));
+        assert_eq!(
+            None,
+            try_bounds((Bound::Excluded(usize::MAX), Bound::Unbounded), 5)
+        );
+    }
+
+    #[test]
+    fn test_bounds() {
+        assert_eq!((0, 5), bounds(.., 5));
+        assert_eq!((1, 1), bounds(1..1, 5));
+        assert_eq!((2, 5), bounds(2.., 5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bounds_out_of_range() {
+        bounds(0..6, 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bounds_overflow() {
+        bounds(..=usize::MAX, 5);
+    }
+}
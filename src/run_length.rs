@@ -0,0 +1,23 @@
+pub fn run_length<T: PartialEq + Clone>(data: &[T]) -> Vec<(T, usize)> {
+    let mut runs: Vec<(T, usize)> = vec![];
+    for x in data {
+        match runs.last_mut() {
+            Some((value, count)) if value == x => *count += 1,
+            _ => runs.push((x.clone(), 1)),
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod test {
+    use super::run_length;
+
+    #[test]
+    fn test_run_length() {
+        let chars = "aaabbc".chars().collect::<Vec<_>>();
+        assert_eq!(vec![('a', 3), ('b', 2), ('c', 1)], run_length(&chars));
+        assert_eq!(Vec::<(i32, usize)>::new(), run_length::<i32>(&[]));
+        assert_eq!(vec![(1, 1), (2, 1), (1, 2)], run_length(&[1, 2, 1, 1]));
+    }
+}
@@ -0,0 +1,201 @@
+use crate::group::Monoid;
+use crate::range::bounds;
+use std::ops::RangeBounds;
+
+#[derive(Clone, Debug)]
+pub struct SegmentTree<M: Monoid + Clone> {
+    len: usize,
+    tree: Vec<M>,
+}
+
+impl<M: Monoid + Clone> SegmentTree<M> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            len: n,
+            tree: (0..2 * n).map(|_| M::identity()).collect::<Vec<_>>(),
+        }
+    }
+
+    pub fn from_slice<U: Clone + Into<M>>(v: &[U]) -> Self {
+        let len = v.len();
+        let mut tree = (0..len).map(|_| M::identity()).collect::<Vec<_>>();
+        tree.extend(v.iter().cloned().map(|e| e.into()));
+        for i in (1..len).rev() {
+            tree[i] = tree[i * 2].apply(&tree[i * 2 + 1]);
+        }
+        Self { len, tree }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn update(&mut self, index: usize, value: impl Into<M>) {
+        assert!(index < self.len());
+        let mut index = index + self.len;
+        self.tree[index] = value.into();
+        while index > 1 {
+            index /= 2;
+            self.tree[index] = self.tree[index * 2].apply(&self.tree[index * 2 + 1]);
+        }
+    }
+
+    pub fn get(&self, index: usize) -> M {
+        assert!(index < self.len());
+        self.tree[index + self.len].clone()
+    }
+
+    pub fn query(&self, range: impl RangeBounds<usize>) -> M {
+        let len = self.len();
+        let (begin, end) = bounds(range, len);
+
+        let mut left = M::identity();
+        let mut right = M::identity();
+        let mut l = begin + len;
+        let mut r = end + len;
+        while l < r {
+            if l & 1 == 1 {
+                left = left.apply(&self.tree[l]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                right = self.tree[r].apply(&right);
+            }
+            l /= 2;
+            r /= 2;
+        }
+        left.apply(&right)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod segment_tree {
+        use super::super::SegmentTree;
+        use crate::group::{Max, Min, Monoid, Sum};
+        use rand::rngs::SmallRng;
+        use rand::{Rng, SeedableRng};
+
+        #[derive(Clone, PartialEq, Debug)]
+        struct Concat(String);
+
+        impl Monoid for Concat {
+            fn identity() -> Self {
+                Concat(String::new())
+            }
+
+            fn apply(&self, rhs: &Self) -> Self {
+                Concat(format!("{}{}", self.0, rhs.0))
+            }
+        }
+
+        fn check<M, F>(data: &[i64], tree: &SegmentTree<M>, project: impl Fn(M) -> i64, fold: F)
+        where
+            M: Monoid + Clone,
+            F: Fn(&[i64]) -> i64,
+        {
+            for l in 0..=data.len() {
+                for r in l..=data.len() {
+                    assert_eq!(fold(&data[l..r]), project(tree.query(l..r)));
+                }
+            }
+        }
+
+        #[test]
+        fn test_len() {
+            assert_eq!(0, SegmentTree::<Sum<i64>>::new(0).len());
+            assert!(SegmentTree::<Sum<i64>>::new(0).is_empty());
+            assert_eq!(10, SegmentTree::<Sum<i64>>::new(10).len());
+            assert_eq!(3, SegmentTree::<Sum<i64>>::from_slice(&[1, 2, 3]).len());
+        }
+
+        #[test]
+        fn test_query() {
+            let mut rng = SmallRng::seed_from_u64(816);
+            for n in 0..20 {
+                let mut data = (0..n)
+                    .map(|_| rng.gen_range(-100, 100))
+                    .collect::<Vec<i64>>();
+                let mut sum = SegmentTree::<Sum<i64>>::from_slice(&data);
+                let mut max = SegmentTree::<Max<i64>>::from_slice(&data);
+                let mut min = SegmentTree::<Min<i64>>::from_slice(&data);
+                for _ in 0..5 {
+                    check(&data, &sum, |m| m.0, |s| s.iter().sum());
+                    check(
+                        &data,
+                        &max,
+                        |m| m.0,
+                        |s| *s.iter().max().unwrap_or(&i64::MIN),
+                    );
+                    check(
+                        &data,
+                        &min,
+                        |m| m.0,
+                        |s| *s.iter().min().unwrap_or(&i64::MAX),
+                    );
+                    if n > 0 {
+                        let i = rng.gen_range(0, n);
+                        data[i] = rng.gen_range(-100, 100);
+                        sum.update(i, data[i]);
+                        max.update(i, data[i]);
+                        min.update(i, data[i]);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_query_bounds() {
+            let tree = SegmentTree::<Sum<i64>>::from_slice(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+            assert_eq!(45, tree.query(..).0);
+            assert_eq!(10, tree.query(..5).0);
+            assert_eq!(35, tree.query(5..).0);
+            assert_eq!(27, tree.query(2..8).0);
+            assert_eq!(15, tree.query(..=5).0);
+            assert_eq!(0, tree.query(3..3).0);
+            assert_eq!(7, tree.get(7).0);
+        }
+
+        #[test]
+        fn test_non_commutative() {
+            let words = ["a", "b", "c", "d", "e"]
+                .iter()
+                .map(|s| Concat(s.to_string()))
+                .collect::<Vec<_>>();
+            let mut tree = SegmentTree::from_slice(&words);
+            assert_eq!(Concat("abcde".to_string()), tree.query(..));
+            assert_eq!(Concat("bcd".to_string()), tree.query(1..4));
+            tree.update(2, Concat("x".to_string()));
+            assert_eq!(Concat("bxd".to_string()), tree.query(1..4));
+        }
+
+        #[derive(Copy, Clone, Debug)]
+        struct MyMax(i64);
+        crate::impl_monoid!(MyMax, i64::MIN, |a, b| a.max(b));
+
+        #[derive(Copy, Clone, Debug)]
+        struct Affine((i64, i64));
+        crate::impl_monoid!(Affine, (1, 0), |f, g| (f.0 * g.0, f.1 * g.0 + g.1));
+
+        #[test]
+        fn test_impl_monoid() {
+            let data = [3, -1, 4, 1, -5, 9, 2, -6];
+            let tree = SegmentTree::<MyMax>::from_slice(&data.map(MyMax));
+            check(
+                &data,
+                &tree,
+                |m| m.0,
+                |s| s.iter().copied().fold(i64::MIN, i64::max),
+            );
+
+            let tree = SegmentTree::<Affine>::from_slice(&[Affine((2, 1)), Affine((3, -2))]);
+            assert_eq!((6, 1), tree.query(..).0);
+            assert_eq!((1, 0), tree.query(1..1).0);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::collections::VecDeque;
+
+fn sliding_by<T: Copy>(data: &[T], k: usize, keep: impl Fn(&T, &T) -> bool) -> Vec<T> {
+    assert!(k > 0);
+    let mut deque = VecDeque::<usize>::new();
+    let mut result = Vec::with_capacity((data.len() + 1).saturating_sub(k));
+    for (i, x) in data.iter().enumerate() {
+        while deque.back().is_some_and(|&j| !keep(&data[j], x)) {
+            deque.pop_back();
+        }
+        deque.push_back(i);
+        if deque[0] + k <= i {
+            deque.pop_front();
+        }
+        if i + 1 >= k {
+            result.push(data[deque[0]]);
+        }
+    }
+    result
+}
+
+pub fn sliding_min<T: Ord + Copy>(data: &[T], k: usize) -> Vec<T> {
+    sliding_by(data, k, |a, b| a < b)
+}
+
+pub fn sliding_max<T: Ord + Copy>(data: &[T], k: usize) -> Vec<T> {
+    sliding_by(data, k, |a, b| a > b)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{sliding_max, sliding_min};
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_sliding_min() {
+        assert_eq!(vec![1, 1, 2, 2], sliding_min(&[3, 1, 4, 2, 5], 2));
+        assert_eq!(vec![1, 1, 2], sliding_min(&[3, 1, 4, 2, 5], 3));
+        assert_eq!(vec![1], sliding_min(&[3, 1, 4, 2, 5], 5));
+        assert!(sliding_min(&[3, 1], 3).is_empty());
+        assert!(sliding_min::<i32>(&[], 1).is_empty());
+    }
+
+    #[test]
+    fn test_sliding_max() {
+        assert_eq!(vec![3, 4, 4, 5], sliding_max(&[3, 1, 4, 2, 5], 2));
+        assert_eq!(vec![2, 2, 2], sliding_max(&[2, 2, 2, 2], 2));
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = SmallRng::seed_from_u64(841);
+        for _ in 0..100 {
+            let n = rng.gen_range(1, 30);
+            let data = (0..n).map(|_| rng.gen_range(-10, 10)).collect::<Vec<i32>>();
+            for &k in [1, n, rng.gen_range(1, n + 1)].iter() {
+                let min = data.windows(k).map(|w| *w.iter().min().unwrap());
+                let max = data.windows(k).map(|w| *w.iter().max().unwrap());
+                assert_eq!(min.collect::<Vec<_>>(), sliding_min(&data, k));
+                assert_eq!(max.collect::<Vec<_>>(), sliding_max(&data, k));
+            }
+        }
+    }
+}
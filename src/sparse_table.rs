@@ -0,0 +1,159 @@
+use crate::group::{Idempotent, Monoid};
+use crate::range::bounds;
+use std::ops::RangeBounds;
+
+fn log2(n: usize) -> usize {
+    (usize::BITS - 1 - n.leading_zeros()) as usize
+}
+
+/// Rectangle queries over a static matrix in O(1) after O(hw log h log w) preprocessing.
+/// Each query combines four overlapping blocks, so the monoid must be idempotent.
+#[derive(Clone, Debug)]
+pub struct SparseTable2D<M: Monoid + Idempotent + Clone> {
+    h: usize,
+    w: usize,
+    // table[a][b][i][j] covers rows i..i + 2^a and columns j..j + 2^b.
+    table: Vec<Vec<Vec<Vec<M>>>>,
+}
+
+impl<M: Monoid + Idempotent + Clone> SparseTable2D<M> {
+    pub fn new<U: Clone + Into<M>>(matrix: &[Vec<U>]) -> Self {
+        let h = matrix.len();
+        let w = matrix.first().map_or(0, |row| row.len());
+        if h == 0 || w == 0 {
+            return Self {
+                h,
+                w,
+                table: vec![],
+            };
+        }
+
+        let base = matrix
+            .iter()
+            .map(|row| {
+                assert_eq!(w, row.len());
+                row.iter().cloned().map(|e| e.into()).collect::<Vec<M>>()
+            })
+            .collect::<Vec<_>>();
+        let mut table: Vec<Vec<Vec<Vec<M>>>> = vec![];
+        for a in 0..=log2(h) {
+            let rows = h + 1 - (1 << a);
+            let mut by_col = vec![];
+            for b in 0..=log2(w) {
+                let cols = w + 1 - (1 << b);
+                let level = (0..rows)
+                    .map(|i| {
+                        (0..cols)
+                            .map(|j| match (a, b) {
+                                (0, 0) => base[i][j].clone(),
+                                (_, 0) => {
+                                    let half = &table[a - 1][0];
+                                    half[i][j].apply(&half[i + (1 << (a - 1))][j])
+                                }
+                                _ => {
+                                    let half: &Vec<Vec<M>> = &by_col[b - 1];
+                                    half[i][j].apply(&half[i][j + (1 << (b - 1))])
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>();
+                by_col.push(level);
+            }
+            table.push(by_col);
+        }
+        Self { h, w, table }
+    }
+
+    pub fn height(&self) -> usize {
+        self.h
+    }
+
+    pub fn width(&self) -> usize {
+        self.w
+    }
+
+    /// Aggregate over the rows in `rows` and the columns in `cols`; the identity when
+    /// either range is empty.
+    pub fn query(&self, rows: impl RangeBounds<usize>, cols: impl RangeBounds<usize>) -> M {
+        let (top, bottom) = bounds(rows, self.h);
+        let (left, right) = bounds(cols, self.w);
+        if top == bottom || left == right {
+            return M::identity();
+        }
+        let (a, b) = (log2(bottom - top), log2(right - left));
+        let level = &self.table[a][b];
+        let (i, j) = (bottom - (1 << a), right - (1 << b));
+        level[top][left]
+            .apply(&level[top][j])
+            .apply(&level[i][left])
+            .apply(&level[i][j])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SparseTable2D;
+    use crate::group::{Max, Min};
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    fn check(matrix: &[Vec<i64>]) {
+        let (h, w) = (matrix.len(), matrix[0].len());
+        let min = SparseTable2D::<Min<i64>>::new(matrix);
+        let max = SparseTable2D::<Max<i64>>::new(matrix);
+        assert_eq!((h, w), (min.height(), min.width()));
+        for top in 0..h {
+            for bottom in top + 1..=h {
+                for left in 0..w {
+                    for right in left + 1..=w {
+                        let cells = matrix[top..bottom]
+                            .iter()
+                            .flat_map(|row| row[left..right].iter());
+                        assert_eq!(
+                            *cells.clone().min().unwrap(),
+                            min.query(top..bottom, left..right).0
+                        );
+                        assert_eq!(*cells.max().unwrap(), max.query(top..bottom, left..right).0);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sparse_table_2d() {
+        let matrix = vec![vec![5, 1, 4], vec![2, 8, 3]];
+        let table = SparseTable2D::<Min<i64>>::new(&matrix);
+        assert_eq!(1, table.query(.., ..).0);
+        assert_eq!(2, table.query(1..=1, ..).0);
+        assert_eq!(3, table.query(.., 2..).0);
+        assert_eq!(8, table.query(1..2, 1..2).0);
+        assert_eq!(i64::MAX, table.query(1..1, ..).0);
+        assert_eq!(i64::MAX, table.query(.., 3..).0);
+    }
+
+    #[test]
+    fn test_sparse_table_2d_random() {
+        let mut rng = SmallRng::seed_from_u64(890);
+        let shapes = [(1, 1), (1, 13), (13, 1), (5, 8), (8, 5), (9, 9)];
+        for &(h, w) in shapes.iter() {
+            let matrix = (0..h)
+                .map(|_| (0..w).map(|_| rng.gen_range(-100, 100)).collect())
+                .collect::<Vec<Vec<i64>>>();
+            check(&matrix);
+        }
+    }
+
+    #[test]
+    fn test_sparse_table_2d_empty() {
+        let table = SparseTable2D::<Max<i64>>::new::<i64>(&[]);
+        assert_eq!(i64::MIN, table.query(.., ..).0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sparse_table_2d_out_of_bounds() {
+        SparseTable2D::<Max<i64>>::new(&[vec![1, 2]]).query(.., 0..3);
+    }
+}
@@ -0,0 +1,116 @@
+/// Every bitmask over `n` elements, from `0` up to `2^n - 1`.
+pub fn subsets(n: usize) -> impl Iterator<Item = u64> {
+    assert!(n < 64);
+    0..1u64 << n
+}
+
+/// Every submask of `mask` in descending order, from `mask` itself down to `0`.
+/// Iterating this for all masks of `n` bits costs O(3^n) in total.
+pub fn submasks(mask: u64) -> impl Iterator<Item = u64> {
+    std::iter::successors(Some(mask), move |&sub| {
+        if sub == 0 {
+            None
+        } else {
+            Some((sub - 1) & mask)
+        }
+    })
+}
+
+fn next_combination(indices: &mut [usize], n: usize) -> bool {
+    let k = indices.len();
+    match (0..k).rev().find(|&i| indices[i] < n - k + i) {
+        Some(i) => {
+            indices[i] += 1;
+            for j in i + 1..k {
+                indices[j] = indices[j - 1] + 1;
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+/// Each `k`-element subset of `0..n` as an ascending `Vec`, in lexicographic order.
+pub fn combinations(n: usize, k: usize) -> impl Iterator<Item = Vec<usize>> {
+    let first = if k <= n { Some((0..k).collect()) } else { None };
+    std::iter::successors(first, move |prev: &Vec<usize>| {
+        let mut next = prev.clone();
+        if next_combination(&mut next, n) {
+            Some(next)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{combinations, submasks, subsets};
+    use crate::combinatorics::Combinatorics;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_subsets() {
+        assert_eq!(vec![0], subsets(0).collect::<Vec<_>>());
+        assert_eq!(vec![0, 1, 2, 3], subsets(2).collect::<Vec<_>>());
+        for n in 0..=12 {
+            assert_eq!(1 << n, subsets(n).count());
+        }
+        assert_eq!(Some((1 << 40) - 1), subsets(40).last());
+    }
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(
+            vec![
+                vec![0, 1],
+                vec![0, 2],
+                vec![0, 3],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 3]
+            ],
+            combinations(4, 2).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![Vec::<usize>::new()],
+            combinations(0, 0).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![vec![0, 1, 2]], combinations(3, 3).collect::<Vec<_>>());
+        assert_eq!(0, combinations(2, 3).count());
+
+        let comb = Combinatorics::new(10, 1_000_000_007);
+        for n in 0..=10 {
+            for k in 0..=n {
+                let all = combinations(n, k).collect::<Vec<_>>();
+                assert_eq!(comb.comb(n, k) as usize, all.len());
+                assert!(all.iter().all(|c| c.len() == k
+                    && c.windows(2).all(|w| w[0] < w[1])
+                    && c.iter().all(|&i| i < n)));
+                assert_eq!(all.len(), all.iter().collect::<HashSet<_>>().len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_submasks() {
+        assert_eq!(
+            vec![0b101, 0b100, 0b001, 0b000],
+            submasks(0b101).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![0], submasks(0).collect::<Vec<_>>());
+        for mask in subsets(8) {
+            let expected = subsets(8)
+                .filter(|&sub| sub & !mask == 0)
+                .collect::<HashSet<_>>();
+            let actual = submasks(mask).collect::<Vec<_>>();
+            assert_eq!(1 << mask.count_ones(), actual.len());
+            assert_eq!(expected, actual.into_iter().collect::<HashSet<_>>());
+        }
+        assert_eq!(1 << 3, submasks(1 << 63 | 1 << 40 | 1).count());
+        assert_eq!(
+            3usize.pow(8),
+            subsets(8).map(|mask| submasks(mask).count()).sum::<usize>()
+        );
+    }
+}
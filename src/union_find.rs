@@ -1,8 +1,13 @@
+use crate::group::{Abelian, Group};
+use std::collections::HashMap;
+use std::rc::Rc;
+
 #[derive(Eq, PartialEq, Clone, Debug)]
 pub struct UnionFind {
     parents: Vec<usize>,
     rank: Vec<usize>,
     size: Vec<usize>,
+    min: Vec<usize>,
 }
 
 impl UnionFind {
@@ -11,9 +16,19 @@ impl UnionFind {
             parents: (0..n).collect(),
             rank: vec![0; n],
             size: vec![1; n],
+            min: (0..n).collect(),
         }
     }
 
+    pub fn push(&mut self) -> usize {
+        let index = self.len();
+        self.parents.push(index);
+        self.rank.push(0);
+        self.size.push(1);
+        self.min.push(index);
+        index
+    }
+
     pub fn merge(&mut self, a: usize, b: usize) {
         let mut a_root: usize = self.root(a);
         let mut b_root: usize = self.root(b);
@@ -27,6 +42,7 @@ impl UnionFind {
             self.rank[a_root] += 1;
         }
         self.size[a_root] += self.size[b_root];
+        self.min[a_root] = self.min[a_root].min(self.min[b_root]);
         self.parents[b_root] = a_root;
     }
 
@@ -39,10 +55,31 @@ impl UnionFind {
         self.size[root]
     }
 
+    /// Smallest index in the set containing `n`, a canonical label independent of merge order.
+    pub fn min_representative(&mut self, n: usize) -> usize {
+        let root = self.root(n);
+        self.min[root]
+    }
+
+    /// Like `size`, but through a shared reference. The root is found without path
+    /// compression, so each call costs O(log n) (bounded by union by rank) and speeds
+    /// up nothing afterwards.
+    pub fn size_of(&self, n: usize) -> usize {
+        let mut root = n;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        self.size[root]
+    }
+
     pub fn len(&self) -> usize {
         self.parents.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
     pub fn groups(&mut self) -> Vec<Vec<usize>> {
         let len = self.parents.len();
         for i in 0..len {
@@ -56,6 +93,15 @@ impl UnionFind {
         ret.into_iter().filter(|v| !v.is_empty()).collect()
     }
 
+    pub fn group_map(&mut self) -> HashMap<usize, Vec<usize>> {
+        let mut ret: HashMap<usize, Vec<usize>> = HashMap::new();
+        for i in 0..self.len() {
+            let root = self.root(i);
+            ret.entry(root).or_default().push(i);
+        }
+        ret
+    }
+
     fn root(&mut self, node: usize) -> usize {
         if self.parents[node] != node {
             self.parents[node] = self.root(self.parents[node]);
@@ -64,10 +110,211 @@ impl UnionFind {
     }
 }
 
+/// Union-find that also tracks potentials in an abelian group: after
+/// `merge(a, b, w)`, `diff(a, b)` is `w`, i.e. `potential(b) = potential(a) + w`.
+#[derive(Clone, Debug)]
+pub struct WeightedUnionFind<T> {
+    parents: Vec<usize>,
+    size: Vec<usize>,
+    weights: Vec<T>,
+}
+
+impl<T: Group + Abelian + Clone + PartialEq> WeightedUnionFind<T> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parents: (0..n).collect(),
+            size: vec![1; n],
+            weights: (0..n).map(|_| T::identity()).collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    fn root(&mut self, node: usize) -> usize {
+        let parent = self.parents[node];
+        if parent != node {
+            let root = self.root(parent);
+            self.weights[node] = self.weights[node].apply(&self.weights[parent]);
+            self.parents[node] = root;
+        }
+        self.parents[node]
+    }
+
+    fn potential(&mut self, node: usize) -> T {
+        self.root(node);
+        self.weights[node].clone()
+    }
+
+    /// Records `potential(b) = potential(a) + w`. Returns `false`, changing
+    /// nothing, if `a` and `b` are already connected with a different difference.
+    pub fn merge(&mut self, a: usize, b: usize, w: impl Into<T>) -> bool {
+        let w = w.into();
+        let (a_root, b_root) = (self.root(a), self.root(b));
+        if a_root == b_root {
+            return self.diff(a, b) == Some(w);
+        }
+        // weight that puts b_root at potential(a) + w - potential(b) relative to a_root
+        let w = w
+            .apply(&self.potential(a))
+            .apply(&self.potential(b).inverse());
+        if self.size[a_root] < self.size[b_root] {
+            self.parents[a_root] = b_root;
+            self.weights[a_root] = w.inverse();
+            self.size[b_root] += self.size[a_root];
+        } else {
+            self.parents[b_root] = a_root;
+            self.weights[b_root] = w;
+            self.size[a_root] += self.size[b_root];
+        }
+        true
+    }
+
+    pub fn is_same(&mut self, a: usize, b: usize) -> bool {
+        self.root(a) == self.root(b)
+    }
+
+    pub fn diff(&mut self, a: usize, b: usize) -> Option<T> {
+        if !self.is_same(a, b) {
+            return None;
+        }
+        Some(self.potential(b).apply(&self.potential(a).inverse()))
+    }
+
+    pub fn size(&mut self, n: usize) -> usize {
+        let root = self.root(n);
+        self.size[root]
+    }
+}
+
+enum Node {
+    Leaf { parent: usize, size: usize },
+    Branch(Rc<Node>, Rc<Node>),
+}
+
+fn build(lo: usize, hi: usize) -> Rc<Node> {
+    if hi - lo == 1 {
+        Rc::new(Node::Leaf {
+            parent: lo,
+            size: 1,
+        })
+    } else {
+        let mid = (lo + hi) / 2;
+        Rc::new(Node::Branch(build(lo, mid), build(mid, hi)))
+    }
+}
+
+fn get(node: &Node, lo: usize, hi: usize, index: usize) -> (usize, usize) {
+    match node {
+        Node::Leaf { parent, size } => (*parent, *size),
+        Node::Branch(left, right) => {
+            let mid = (lo + hi) / 2;
+            if index < mid {
+                get(left, lo, mid, index)
+            } else {
+                get(right, mid, hi, index)
+            }
+        }
+    }
+}
+
+fn set(node: &Rc<Node>, lo: usize, hi: usize, index: usize, leaf: (usize, usize)) -> Rc<Node> {
+    match node.as_ref() {
+        Node::Leaf { .. } => Rc::new(Node::Leaf {
+            parent: leaf.0,
+            size: leaf.1,
+        }),
+        Node::Branch(left, right) => {
+            let mid = (lo + hi) / 2;
+            if index < mid {
+                Rc::new(Node::Branch(set(left, lo, mid, index, leaf), right.clone()))
+            } else {
+                Rc::new(Node::Branch(left.clone(), set(right, mid, hi, index, leaf)))
+            }
+        }
+    }
+}
+
+/// Union-find whose `merge` returns a new version; earlier versions stay valid and
+/// share all untouched nodes. Union by size without path compression, so every
+/// operation is `O(log^2 n)`.
+#[derive(Clone)]
+pub struct PersistentUnionFind {
+    len: usize,
+    root: Option<Rc<Node>>,
+}
+
+impl PersistentUnionFind {
+    pub fn new(n: usize) -> Self {
+        Self {
+            len: n,
+            root: if n == 0 { None } else { Some(build(0, n)) },
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn entry(&self, index: usize) -> (usize, usize) {
+        assert!(index < self.len);
+        get(self.root.as_ref().unwrap(), 0, self.len, index)
+    }
+
+    fn root(&self, node: usize) -> usize {
+        let mut node = node;
+        loop {
+            let (parent, _) = self.entry(node);
+            if parent == node {
+                return node;
+            }
+            node = parent;
+        }
+    }
+
+    pub fn merge(&self, a: usize, b: usize) -> Self {
+        let (mut a_root, mut b_root) = (self.root(a), self.root(b));
+        if a_root == b_root {
+            return self.clone();
+        }
+        let (mut a_size, mut b_size) = (self.entry(a_root).1, self.entry(b_root).1);
+        if a_size < b_size {
+            std::mem::swap(&mut a_root, &mut b_root);
+            std::mem::swap(&mut a_size, &mut b_size);
+        }
+        let root = self.root.as_ref().unwrap();
+        let root = set(root, 0, self.len, b_root, (a_root, b_size));
+        let root = set(&root, 0, self.len, a_root, (a_root, a_size + b_size));
+        Self {
+            len: self.len,
+            root: Some(root),
+        }
+    }
+
+    pub fn is_same(&self, a: usize, b: usize) -> bool {
+        self.root(a) == self.root(b)
+    }
+
+    pub fn size(&self, n: usize) -> usize {
+        self.entry(self.root(n)).1
+    }
+}
+
 #[cfg(test)]
 mod test {
     mod union_find {
         use super::super::UnionFind;
+        use rand::rngs::SmallRng;
+        use rand::{Rng, SeedableRng};
         use std::collections::HashSet;
 
         macro_rules! uf {
@@ -321,5 +568,246 @@ mod test {
                     .collect::<Vec<_>>()
             );
         }
+
+        #[test]
+        fn test_group_map() {
+            let mut uf = uf!(
+                length: 8,
+                0 => 3,
+                3 => 5,
+                1 => 7,
+                6 => 6
+            );
+
+            let map = uf.group_map();
+            assert_eq!(5, map.len());
+            for (&root, members) in map.iter() {
+                assert!(members.contains(&root));
+                assert!(members.iter().all(|&m| uf.is_same(m, root)));
+            }
+            let mut expected = uf
+                .groups()
+                .into_iter()
+                .map(|v| v.into_iter().collect::<HashSet<_>>())
+                .collect::<Vec<_>>();
+            let mut actual = map
+                .into_values()
+                .map(|v| v.into_iter().collect::<HashSet<_>>())
+                .collect::<Vec<_>>();
+            expected.sort_by_key(|s| *s.iter().min().unwrap());
+            actual.sort_by_key(|s| *s.iter().min().unwrap());
+            assert_eq!(expected, actual);
+
+            assert!(UnionFind::new(0).group_map().is_empty());
+        }
+
+        #[test]
+        fn test_push() {
+            let mut uf = uf!(
+                length: 3,
+                0 => 1
+            );
+
+            assert_eq!(3, uf.push());
+            assert_eq!(4, uf.push());
+            assert_eq!(5, uf.push());
+            assert_eq!(6, uf.len());
+            assert_eq!(1, uf.size(3));
+            assert!(!uf.is_same(3, 4));
+
+            uf.merge(3, 0);
+            uf.merge(4, 5);
+            assert!(uf.is_same(3, 1));
+            assert!(uf.is_same(5, 4));
+            assert!(!uf.is_same(3, 4));
+            assert!(!uf.is_same(2, 5));
+            assert_eq!(3, uf.size(1));
+            assert_eq!(2, uf.size(5));
+
+            uf.merge(2, 5);
+            assert_eq!(3, uf.size(4));
+            assert_eq!(2, uf.groups().len());
+
+            let mut uf = UnionFind::new(0);
+            assert_eq!(0, uf.push());
+            assert_eq!(1, uf.push());
+            uf.merge(0, 1);
+            assert!(uf.is_same(0, 1));
+        }
+
+        #[test]
+        fn test_size_of() {
+            let uf = uf!(
+                length: 6,
+                0 => 1,
+                1 => 2,
+                4 => 5
+            );
+            assert_eq!(
+                vec![3, 3, 3, 1, 2, 2],
+                (0..6).map(|i| uf.size_of(i)).collect::<Vec<_>>()
+            );
+
+            let mut rng = SmallRng::seed_from_u64(891);
+            let n = 50;
+            let mut uf = UnionFind::new(n);
+            for _ in 0..40 {
+                uf.merge(rng.gen_range(0, n), rng.gen_range(0, n));
+                let shared = &uf;
+                let sizes = (0..n).map(|i| shared.size_of(i)).collect::<Vec<_>>();
+                for (i, size) in sizes.into_iter().enumerate() {
+                    assert_eq!(uf.size(i), size);
+                }
+            }
+        }
+
+        #[test]
+        fn test_min_representative() {
+            let mut uf = uf!(
+                length: 8,
+                5 => 3,
+                3 => 7,
+                6 => 1,
+                1 => 4
+            );
+            assert_eq!(
+                vec![0, 1, 2, 3, 1, 3, 1, 3],
+                (0..8).map(|i| uf.min_representative(i)).collect::<Vec<_>>()
+            );
+
+            uf.merge(7, 4);
+            let index = uf.push();
+            uf.merge(index, 2);
+            assert_eq!(
+                vec![0, 1, 2, 1, 1, 1, 1, 1, 2],
+                (0..9).map(|i| uf.min_representative(i)).collect::<Vec<_>>()
+            );
+
+            let mut rng = SmallRng::seed_from_u64(892);
+            let n = 40;
+            let mut uf = UnionFind::new(n);
+            for _ in 0..30 {
+                uf.merge(rng.gen_range(0, n), rng.gen_range(0, n));
+            }
+            for i in 0..n {
+                let expected = (0..n).find(|&j| uf.is_same(i, j)).unwrap();
+                assert_eq!(expected, uf.min_representative(i));
+            }
+        }
+    }
+
+    mod persistent_union_find {
+        use super::super::{PersistentUnionFind, UnionFind};
+        use rand::rngs::SmallRng;
+        use rand::{Rng, SeedableRng};
+
+        #[test]
+        fn test_versions() {
+            let v0 = PersistentUnionFind::new(5);
+            let v1 = v0.merge(0, 1);
+            let v2 = v1.merge(2, 3);
+            let v3 = v1.merge(1, 4);
+
+            assert!(!v0.is_same(0, 1));
+            assert_eq!(1, v0.size(0));
+
+            assert!(v1.is_same(0, 1));
+            assert!(!v1.is_same(2, 3));
+            assert!(!v1.is_same(1, 4));
+            assert_eq!(2, v1.size(1));
+
+            assert!(v2.is_same(2, 3));
+            assert!(!v2.is_same(0, 4));
+            assert_eq!(2, v2.size(3));
+
+            assert!(v3.is_same(0, 4));
+            assert!(!v3.is_same(2, 3));
+            assert_eq!(3, v3.size(4));
+
+            assert!(PersistentUnionFind::new(0).is_empty());
+            assert_eq!(5, v3.len());
+        }
+
+        #[test]
+        fn test_random() {
+            let mut rng = SmallRng::seed_from_u64(866);
+            let n = 30;
+            let mut versions = vec![(PersistentUnionFind::new(n), UnionFind::new(n))];
+            for _ in 0..200 {
+                let base = rng.gen_range(0, versions.len());
+                let (a, b) = (rng.gen_range(0, n), rng.gen_range(0, n));
+                let (puf, uf) = &versions[base];
+                let mut uf = uf.clone();
+                uf.merge(a, b);
+                versions.push((puf.merge(a, b), uf));
+            }
+            for (puf, uf) in versions.iter_mut() {
+                for a in 0..n {
+                    assert_eq!(uf.size(a), puf.size(a));
+                    for b in 0..n {
+                        assert_eq!(uf.is_same(a, b), puf.is_same(a, b));
+                    }
+                }
+            }
+        }
+    }
+
+    mod weighted_union_find {
+        use super::super::WeightedUnionFind;
+        use crate::group::{Sum, Xor};
+        use rand::rngs::SmallRng;
+        use rand::{Rng, SeedableRng};
+
+        #[test]
+        fn test_sum() {
+            let mut uf = WeightedUnionFind::<Sum<i64>>::new(5);
+            assert!(uf.merge(0, 1, 3));
+            assert!(uf.merge(1, 2, -5));
+            assert!(uf.merge(3, 4, 10));
+            assert_eq!(Some(Sum(-2)), uf.diff(0, 2));
+            assert_eq!(Some(Sum(2)), uf.diff(2, 0));
+            assert_eq!(None, uf.diff(0, 3));
+
+            assert!(uf.merge(4, 2, 1));
+            assert_eq!(Some(Sum(13)), uf.diff(3, 0));
+            assert_eq!(5, uf.size(4));
+            assert!(uf.merge(0, 3, -13));
+            assert!(!uf.merge(0, 3, 13));
+            assert_eq!(Some(Sum(-13)), uf.diff(0, 3));
+        }
+
+        #[test]
+        fn test_xor() {
+            let mut uf = WeightedUnionFind::<Xor<u64>>::new(4);
+            assert!(uf.merge(0, 1, 0b101u64));
+            assert!(uf.merge(2, 1, 0b011u64));
+            assert_eq!(Some(Xor(0b110)), uf.diff(0, 2));
+            assert_eq!(Some(Xor(0b110)), uf.diff(2, 0));
+            assert!(uf.merge(2, 0, 0b110u64));
+            assert!(!uf.merge(2, 0, 0b111u64));
+            assert!(!uf.is_same(0, 3));
+        }
+
+        #[test]
+        fn test_random() {
+            let mut rng = SmallRng::seed_from_u64(867);
+            let n = 40;
+            let potential = (0..n)
+                .map(|_| rng.gen_range(-100, 100))
+                .collect::<Vec<i64>>();
+            let mut uf = WeightedUnionFind::<Sum<i64>>::new(n);
+            for _ in 0..100 {
+                let (a, b) = (rng.gen_range(0, n), rng.gen_range(0, n));
+                assert!(uf.merge(a, b, potential[b] - potential[a]));
+                assert!(!uf.merge(a, b, potential[b] - potential[a] + 1));
+            }
+            for a in 0..n {
+                for b in 0..n {
+                    if uf.is_same(a, b) {
+                        assert_eq!(Some(Sum(potential[b] - potential[a])), uf.diff(a, b));
+                    }
+                }
+            }
+        }
     }
 }
@@ -1,22 +1,96 @@
-use std::fmt::Debug;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Add, Mul, Neg, Sub};
 
+pub const EPS: f64 = 1e-9;
+
+pub trait Scalar:
+    Copy
+    + PartialOrd
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    fn zero() -> Self;
+    fn to_f64(self) -> f64;
+
+    /// Tolerance under which a value counts as zero in sign tests.
+    fn eps() -> Self {
+        Self::zero()
+    }
+
+    fn abs(self) -> Self {
+        if self < Self::zero() {
+            -self
+        } else {
+            self
+        }
+    }
+}
+
+impl Scalar for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn eps() -> Self {
+        EPS
+    }
+}
+
+impl Scalar for i64 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+#[allow(clippy::len_without_is_empty)]
 pub trait Vector2D: Sized {
-    fn new(x: f64, y: f64) -> Self;
-    fn x(&self) -> f64;
-    fn y(&self) -> f64;
+    type Scalar: Scalar;
+
+    fn new(x: Self::Scalar, y: Self::Scalar) -> Self;
+    fn x(&self) -> Self::Scalar;
+    fn y(&self) -> Self::Scalar;
 
     fn origin() -> Self {
-        Self::new(0.0, 0.0)
+        Self::new(Self::Scalar::zero(), Self::Scalar::zero())
     }
 
-    fn dot(&self, rhs: &Self) -> f64 {
+    fn dot(&self, rhs: &Self) -> Self::Scalar {
         self.x() * rhs.x() + self.y() * rhs.y()
     }
 
-    fn cross(&self, rhs: &Self) -> f64 {
+    fn cross(&self, rhs: &Self) -> Self::Scalar {
         self.x() * rhs.y() - self.y() * rhs.x()
     }
 
+    /// Whether both coordinates differ by at most `eps`.
+    fn approx_eq(&self, other: &Self, eps: f64) -> bool {
+        (self.x() - other.x()).to_f64().abs() <= eps && (self.y() - other.y()).to_f64().abs() <= eps
+    }
+
+    /// Sign of `self.cross(rhs)`, treating magnitudes within `Scalar::eps()` as zero.
+    fn cross_sign(&self, rhs: &Self) -> Ordering {
+        let cross = self.cross(rhs);
+        if cross > Self::Scalar::eps() {
+            Ordering::Greater
+        } else if cross < -Self::Scalar::eps() {
+            Ordering::Less
+        } else {
+            Ordering::Equal
+        }
+    }
+
     fn length(&self) -> f64 {
         self.distance(&<Self as Vector2D>::origin())
     }
@@ -26,26 +100,93 @@ pub trait Vector2D: Sized {
     }
 
     fn distance(&self, rhs: &Self) -> f64 {
-        (self.x() - rhs.x()).hypot(self.y() - rhs.y()).abs()
+        (self.x() - rhs.x())
+            .to_f64()
+            .hypot((self.y() - rhs.y()).to_f64())
+            .abs()
+    }
+
+    fn manhattan(&self, rhs: &Self) -> Self::Scalar {
+        (self.x() - rhs.x()).abs() + (self.y() - rhs.y()).abs()
+    }
+
+    fn chebyshev(&self, rhs: &Self) -> Self::Scalar {
+        let dx = (self.x() - rhs.x()).abs();
+        let dy = (self.y() - rhs.y()).abs();
+        if dx < dy {
+            dy
+        } else {
+            dx
+        }
     }
 
     fn argument(&self) -> f64 {
-        self.y().atan2(self.x())
+        self.y().to_f64().atan2(self.x().to_f64())
     }
 
-    fn rotate(&self, rad: f64) -> Self {
+    fn angle(&self, rhs: &Self) -> f64 {
+        let angle = self.cross(rhs).to_f64().atan2(self.dot(rhs).to_f64());
+        if angle == -std::f64::consts::PI {
+            std::f64::consts::PI
+        } else {
+            angle
+        }
+    }
+
+    fn rotate(&self, rad: f64) -> Self
+    where
+        Self: Vector2D<Scalar = f64>,
+    {
         Self::new(
             self.x() * rad.cos() - self.y() * rad.sin(),
             self.x() * rad.sin() + self.y() * rad.cos(),
         )
     }
 
-    fn unit(&self) -> Self {
+    fn rotate_around(&self, pivot: &Self, rad: f64) -> Self
+    where
+        Self: Vector2D<Scalar = f64>,
+    {
+        self.sub(pivot).rotate(rad).add(pivot)
+    }
+
+    /// Point at parameter `t` on the line through `self` (`t = 0`) and `other` (`t = 1`).
+    fn lerp(&self, other: &Self, t: f64) -> Self
+    where
+        Self: Vector2D<Scalar = f64>,
+    {
+        Self::new(
+            self.x() * (1.0 - t) + other.x() * t,
+            self.y() * (1.0 - t) + other.y() * t,
+        )
+    }
+
+    /// Undefined for the zero vector (the result is NaN); see `try_unit`.
+    fn unit(&self) -> Self
+    where
+        Self: Vector2D<Scalar = f64>,
+    {
         let len = self.len();
         Self::new(self.x() / len, self.y() / len)
     }
 
-    fn normal(&self) -> Self {
+    /// `None` when the length is within `EPS` of zero.
+    fn try_unit(&self) -> Option<Self>
+    where
+        Self: Vector2D<Scalar = f64>,
+    {
+        if self.len() < EPS {
+            None
+        } else {
+            Some(self.unit())
+        }
+    }
+
+    /// Undefined for the zero vector, like `unit`.
+    fn normal(&self) -> Self
+    where
+        Self: Vector2D<Scalar = f64>,
+    {
         let len = self.len();
         Self::new(-self.y() / len, self.x() / len)
     }
@@ -63,6 +204,8 @@ pub trait Vector2D: Sized {
 pub struct Vec2(pub f64, pub f64);
 
 impl Vector2D for Vec2 {
+    type Scalar = f64;
+
     fn new(x: f64, y: f64) -> Self {
         Vec2(x, y)
     }
@@ -76,6 +219,27 @@ impl Vector2D for Vec2 {
     }
 }
 
+impl From<(f64, f64)> for Vec2 {
+    fn from((x, y): (f64, f64)) -> Self {
+        Vec2(x, y)
+    }
+}
+
+impl From<Vec2> for (f64, f64) {
+    fn from(v: Vec2) -> Self {
+        (v.0, v.1)
+    }
+}
+
+impl Display for Vec2 {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*} {:.*}", precision, self.0, precision, self.1),
+            None => write!(f, "{} {}", self.0, self.1),
+        }
+    }
+}
+
 impl std::ops::Add for Vec2 {
     type Output = Vec2;
 
@@ -92,6 +256,77 @@ impl std::ops::Sub for Vec2 {
     }
 }
 
+impl std::ops::Mul<f64> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Vec2(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl std::ops::Div<f64> for Vec2 {
+    type Output = Vec2;
+
+    fn div(self, rhs: f64) -> Self::Output {
+        Vec2(self.0 / rhs, self.1 / rhs)
+    }
+}
+
+impl std::ops::Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Self::Output {
+        Vec2(-self.0, -self.1)
+    }
+}
+
+impl std::ops::AddAssign for Vec2 {
+    fn add_assign(&mut self, rhs: Vec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl std::ops::SubAssign for Vec2 {
+    fn sub_assign(&mut self, rhs: Vec2) {
+        *self = *self - rhs;
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct IVec2(pub i64, pub i64);
+
+impl Vector2D for IVec2 {
+    type Scalar = i64;
+
+    fn new(x: i64, y: i64) -> Self {
+        IVec2(x, y)
+    }
+
+    fn x(&self) -> i64 {
+        self.0
+    }
+
+    fn y(&self) -> i64 {
+        self.1
+    }
+}
+
+impl std::ops::Add for IVec2 {
+    type Output = IVec2;
+
+    fn add(self, rhs: IVec2) -> Self::Output {
+        <Self as Vector2D>::add(&self, &rhs)
+    }
+}
+
+impl std::ops::Sub for IVec2 {
+    type Output = IVec2;
+
+    fn sub(self, rhs: IVec2) -> Self::Output {
+        <Self as Vector2D>::sub(&self, &rhs)
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum CCW {
     Clockwise,
@@ -102,17 +337,22 @@ pub enum CCW {
 }
 
 impl CCW {
+    #[allow(clippy::self_named_constructors)]
     pub fn ccw<T: Vector2D>(a: T, b: T, c: T) -> Self {
+        Self::ccw_eps(a, b, c, T::Scalar::zero())
+    }
+
+    pub fn ccw_eps<T: Vector2D>(a: T, b: T, c: T, eps: T::Scalar) -> Self {
         let ab = b.sub(&a);
         let ac = c.sub(&a);
         let det = ab.cross(&ac);
-        if det > 0.0 {
+        if det > eps {
             CCW::CounterClockwise
-        } else if det < 0.0 {
+        } else if det < -eps {
             CCW::Clockwise
-        } else if ab.dot(&ac) < 0.0 {
+        } else if ab.dot(&ac) < T::Scalar::zero() {
             CCW::CAB
-        } else if ab.len() < ac.len() {
+        } else if ab.dot(&ab) < ac.dot(&ac) {
             CCW::ABC
         } else {
             CCW::ACB
@@ -120,6 +360,24 @@ impl CCW {
     }
 }
 
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Turn {
+    Left,
+    Right,
+    Straight,
+}
+
+impl Turn {
+    /// Direction of the turn `a -> b -> c` taken at the pivot `b`.
+    pub fn of<T: Vector2D>(a: &T, b: &T, c: &T) -> Self {
+        match b.sub(a).cross_sign(&c.sub(b)) {
+            Ordering::Greater => Turn::Left,
+            Ordering::Less => Turn::Right,
+            Ordering::Equal => Turn::Straight,
+        }
+    }
+}
+
 pub struct LineSegment<T: Vector2D + PartialEq + Debug>(T, T);
 
 impl<T> LineSegment<T>
@@ -130,6 +388,10 @@ where
         Self(a, b)
     }
 
+    pub fn endpoints(&self) -> (&T, &T) {
+        (&self.0, &self.1)
+    }
+
     pub fn length(&self) -> f64 {
         self.1.sub(&self.0).length()
     }
@@ -152,19 +414,183 @@ where
         let det_cd_ca = cd.cross(&ca);
         let det_cd_cb = cd.cross(&cb);
 
-        det_ab_ac * det_ab_ad < 0.0 && det_cd_ca * det_cd_cb < 0.0
+        let zero = T::Scalar::zero();
+        det_ab_ac * det_ab_ad < zero && det_cd_ca * det_cd_cb < zero
+    }
+
+    /// Like `is_crossing`, but also counts touching endpoints and collinear overlaps.
+    pub fn intersects_inclusive(&self, rhs: &Self) -> bool {
+        let zero = T::Scalar::zero();
+        let side = |s: &Self, p: &T| s.1.sub(&s.0).cross(&p.sub(&s.0));
+        let (d1, d2) = (side(self, &rhs.0), side(self, &rhs.1));
+        let (d3, d4) = (side(rhs, &self.0), side(rhs, &self.1));
+        if d1 * d2 < zero && d3 * d4 < zero {
+            return true;
+        }
+        self.contains(&rhs.0)
+            || self.contains(&rhs.1)
+            || rhs.contains(&self.0)
+            || rhs.contains(&self.1)
+    }
+
+    pub fn contains(&self, p: &T) -> bool {
+        self.contains_eps(p, T::Scalar::zero())
+    }
+
+    pub fn contains_eps(&self, p: &T, eps: T::Scalar) -> bool {
+        let pa = self.0.sub(p);
+        let pb = self.1.sub(p);
+        pa.cross(&pb).abs() <= eps && pa.dot(&pb) <= eps
+    }
+
+    pub fn distance_to_point(&self, p: &T) -> f64 {
+        let a = &self.0;
+        let b = &self.1;
+        let ab = b.sub(a);
+        let ap = p.sub(a);
+        let dot = ap.dot(&ab);
+        if dot <= T::Scalar::zero() {
+            p.distance(a)
+        } else if dot >= ab.dot(&ab) {
+            p.distance(b)
+        } else {
+            ab.cross(&ap).to_f64().abs() / ab.length()
+        }
+    }
+}
+
+impl<T> LineSegment<T>
+where
+    T: Vector2D<Scalar = f64> + PartialEq + Debug,
+{
+    /// Returns the point where the segments meet, including touching endpoints.
+    /// Parallel segments yield `None`, even when they overlap collinearly.
+    pub fn intersection(&self, rhs: &Self) -> Option<T> {
+        let a = &self.0;
+        let c = &rhs.0;
+        let ab = self.1.sub(a);
+        let cd = rhs.1.sub(c);
+        let ac = c.sub(a);
+
+        let det = ab.cross(&cd);
+        if det == 0.0 {
+            return None;
+        }
+        let t = ac.cross(&cd) / det;
+        let u = ac.cross(&ab) / det;
+        if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+            Some(T::new(a.x() + ab.x() * t, a.y() + ab.y() * t))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Circle {
+    pub center: Vec2,
+    pub radius: f64,
+}
+
+impl Circle {
+    pub fn new(center: Vec2, radius: f64) -> Self {
+        Self { center, radius }
+    }
+
+    pub fn intersect_line(&self, a: &Vec2, b: &Vec2) -> Vec<Vec2> {
+        let ab = *b - *a;
+        let t = (self.center - *a).dot(&ab) / ab.dot(&ab);
+        let foot = *a + ab * t;
+        let d = self.center.distance(&foot);
+        if d > self.radius + EPS {
+            vec![]
+        } else if d >= self.radius - EPS {
+            vec![foot]
+        } else {
+            let offset = ab.unit() * (self.radius * self.radius - d * d).sqrt();
+            vec![foot - offset, foot + offset]
+        }
+    }
+
+    pub fn intersect_circle(&self, other: &Circle) -> Vec<Vec2> {
+        let d = self.center.distance(&other.center);
+        let (r1, r2) = (self.radius, other.radius);
+        if d < EPS || d > r1 + r2 + EPS || d < (r1 - r2).abs() - EPS {
+            return vec![];
+        }
+        let direction = (other.center - self.center).unit();
+        let a = (r1 * r1 - r2 * r2 + d * d) / (2.0 * d);
+        let base = self.center + direction * a;
+        if (d - (r1 + r2)).abs() <= EPS || (d - (r1 - r2).abs()).abs() <= EPS {
+            return vec![base];
+        }
+        let offset = direction.normal() * (r1 * r1 - a * a).max(0.0).sqrt();
+        vec![base - offset, base + offset]
     }
 }
 
 #[cfg(test)]
 mod test {
     mod vec2 {
-        use super::super::{Vec2, Vector2D};
+        use super::super::{IVec2, Vec2, Vector2D, EPS};
         use std::f64::consts::PI;
 
         fn vec2_delta_eq(a: Vec2, b: Vec2) -> bool {
-            const DELTA: f64 = 1e-10;
-            (a.x() - b.x()).abs() <= DELTA && (a.y() - b.y()).abs() <= DELTA
+            a.approx_eq(&b, 1e-10)
+        }
+
+        #[test]
+        fn test_rotate_around() {
+            let pivot = Vec2(2.0, 1.0);
+            assert!(Vec2(3.0, 1.0)
+                .rotate_around(&pivot, PI / 2.0)
+                .approx_eq(&Vec2(2.0, 2.0), EPS));
+            assert!(Vec2(4.0, 3.0)
+                .rotate_around(&pivot, -PI / 2.0)
+                .approx_eq(&Vec2(4.0, -1.0), EPS));
+            assert!(Vec2(0.0, 0.0)
+                .rotate_around(&pivot, PI)
+                .approx_eq(&Vec2(4.0, 2.0), EPS));
+            assert_eq!(pivot, pivot.rotate_around(&pivot, 1.234));
+        }
+
+        #[test]
+        fn test_try_unit() {
+            assert_eq!(None, Vec2(0.0, 0.0).try_unit());
+            assert_eq!(None, Vec2(1e-12, -1e-12).try_unit());
+            assert_eq!(Some(Vec2(1.0, 0.0)), Vec2(1.0, 0.0).try_unit());
+            assert_eq!(Some(Vec2(0.0, -1.0)), Vec2(0.0, -1.0).try_unit());
+            assert!(Vec2(3.0, 4.0)
+                .try_unit()
+                .unwrap()
+                .approx_eq(&Vec2(0.6, 0.8), EPS));
+        }
+
+        #[test]
+        fn test_lerp() {
+            let a = Vec2(1.0, -2.0);
+            let b = Vec2(4.0, 6.0);
+            assert_eq!(a, a.lerp(&b, 0.0));
+            assert_eq!(b, a.lerp(&b, 1.0));
+            assert!(a.lerp(&b, 0.5).approx_eq(&Vec2(2.5, 2.0), EPS));
+            assert!(a.lerp(&b, 0.25).approx_eq(&Vec2(1.75, 0.0), EPS));
+            assert!(a.lerp(&b, 2.0).approx_eq(&Vec2(7.0, 14.0), EPS));
+        }
+
+        #[test]
+        fn test_approx_eq() {
+            let a = Vec2(0.1 + 0.2, 1.0);
+            assert_ne!(Vec2(0.3, 1.0), a);
+            assert!(Vec2(0.3, 1.0).approx_eq(&a, EPS));
+            assert!(a.approx_eq(&Vec2(0.3005, 0.9995), 1e-3));
+            assert!(!a.approx_eq(&Vec2(0.302, 1.0), 1e-3));
+            assert!(!a.approx_eq(&Vec2(0.3, 1.002), 1e-3));
+            assert!(Vec2(1.0, 1.0)
+                .rotate(PI * 2.0)
+                .approx_eq(&Vec2(1.0, 1.0), EPS));
+            assert!(Vec2(2.0, 3.0).approx_eq(&Vec2(2.0, 3.0), 0.0));
+            assert!(IVec2(3, 4).approx_eq(&IVec2(4, 3), 1.0));
+            assert!(!IVec2(3, 4).approx_eq(&IVec2(5, 4), 1.0));
         }
 
         #[test]
@@ -347,5 +773,358 @@ mod test {
                 Vec2::new(1.0, -1.0).sub(&Vec2::new(-1.0, 1.0))
             );
         }
+
+        #[test]
+        fn test_mul() {
+            assert_eq!(Vec2(2.0, 4.0), Vec2(1.0, 2.0) * 2.0);
+            assert_eq!(Vec2(-0.5, 0.0), Vec2(1.0, 0.0) * -0.5);
+        }
+
+        #[test]
+        fn test_div() {
+            assert_eq!(Vec2(0.5, 1.0), Vec2(1.0, 2.0) / 2.0);
+            assert_eq!(Vec2(-2.0, 4.0), Vec2(1.0, -2.0) / -0.5);
+        }
+
+        #[test]
+        fn test_neg() {
+            assert_eq!(Vec2(-1.0, 1.0), -Vec2(1.0, -1.0));
+            assert_eq!(Vec2(1.0, 2.0), -(-Vec2(1.0, 2.0)));
+        }
+
+        #[test]
+        fn test_assign() {
+            let mut v = Vec2(1.0, 2.0);
+            v += Vec2(3.0, -1.0);
+            assert_eq!(Vec2(4.0, 1.0), v);
+            v -= Vec2(4.0, 4.0);
+            assert_eq!(Vec2(0.0, -3.0), v);
+        }
+
+        #[test]
+        fn test_angle() {
+            assert_eq!(PI / 2.0, Vec2(1.0, 0.0).angle(&Vec2(0.0, 1.0)));
+            assert_eq!(-PI / 2.0, Vec2(0.0, 1.0).angle(&Vec2(1.0, 0.0)));
+            assert_eq!(PI, Vec2(1.0, 0.0).angle(&Vec2(-1.0, 0.0)));
+            assert_eq!(PI, Vec2(-1.0, 0.0).angle(&Vec2(1.0, 0.0)));
+            assert_eq!(PI, Vec2(1.0, -0.0).angle(&Vec2(-1.0, 0.0)));
+            assert_eq!(0.0, Vec2(2.0, 2.0).angle(&Vec2(1.0, 1.0)));
+            assert!((Vec2(1.0, 1.0).angle(&Vec2(-1.0, 0.0)) - PI * 3.0 / 4.0).abs() < 1e-10);
+            assert!((Vec2(-1.0, 0.0).angle(&Vec2(1.0, 1.0)) + PI * 3.0 / 4.0).abs() < 1e-10);
+            assert!((Vec2(-1.0, -1.0).angle(&Vec2(-1.0, 1.0)) - -PI / 2.0).abs() < 1e-10);
+        }
+
+        #[test]
+        fn test_display() {
+            assert_eq!("1 2.5", format!("{}", Vec2(1.0, 2.5)));
+            assert_eq!("-1 0", format!("{}", Vec2(-1.0, 0.0)));
+            assert_eq!("1.00 2.50", format!("{:.2}", Vec2(1.0, 2.5)));
+            assert_eq!(
+                "0.333 -0.667",
+                format!("{:.3}", Vec2(1.0 / 3.0, -2.0 / 3.0))
+            );
+        }
+
+        #[test]
+        fn test_manhattan() {
+            assert_eq!(0.0, Vec2(1.0, 2.0).manhattan(&Vec2(1.0, 2.0)));
+            assert_eq!(3.0, Vec2(1.0, 0.0).manhattan(&Vec2(4.0, 0.0)));
+            assert_eq!(4.0, Vec2(0.0, -2.0).manhattan(&Vec2(0.0, 2.0)));
+            assert_eq!(6.0, Vec2(-1.0, -1.0).manhattan(&Vec2(2.0, 2.0)));
+            assert_eq!(7.0, Vec2(1.0, 5.0).manhattan(&Vec2(-1.0, 0.0)));
+        }
+
+        #[test]
+        fn test_chebyshev() {
+            assert_eq!(0.0, Vec2(1.0, 2.0).chebyshev(&Vec2(1.0, 2.0)));
+            assert_eq!(3.0, Vec2(1.0, 0.0).chebyshev(&Vec2(4.0, 0.0)));
+            assert_eq!(4.0, Vec2(0.0, -2.0).chebyshev(&Vec2(0.0, 2.0)));
+            assert_eq!(3.0, Vec2(-1.0, -1.0).chebyshev(&Vec2(2.0, 2.0)));
+            assert_eq!(5.0, Vec2(1.0, 5.0).chebyshev(&Vec2(-1.0, 0.0)));
+
+            let points = [Vec2(0.0, 0.0), Vec2(3.0, -4.0), Vec2(-2.5, 1.5)];
+            for a in points.iter() {
+                for b in points.iter() {
+                    assert!(a.manhattan(b) >= a.chebyshev(b));
+                    assert!(a.manhattan(b) >= a.distance(b));
+                    assert!(a.distance(b) >= a.chebyshev(b));
+                }
+            }
+        }
+
+        #[test]
+        fn test_from_tuple() {
+            let p: Vec2 = (1.0, 2.0).into();
+            assert_eq!(Vec2(1.0, 2.0), p);
+            assert_eq!(Vec2(-3.5, 0.0), Vec2::from((-3.5, 0.0)));
+
+            let (x, y): (f64, f64) = Vec2(1.0, 2.0).into();
+            assert_eq!((1.0, 2.0), (x, y));
+            for &t in &[(0.0, 0.0), (1.5, -2.5), (-1e9, 1e-9)] {
+                assert_eq!(t, <(f64, f64)>::from(Vec2::from(t)));
+            }
+        }
+    }
+
+    mod line_segment {
+        use super::super::{IVec2, LineSegment, Vec2, EPS};
+
+        #[test]
+        fn test_intersection() {
+            let s = LineSegment::new(Vec2(0.0, 0.0), Vec2(2.0, 2.0));
+            let t = LineSegment::new(Vec2(0.0, 2.0), Vec2(2.0, 0.0));
+            assert_eq!(Some(Vec2(1.0, 1.0)), s.intersection(&t));
+            assert_eq!(Some(Vec2(1.0, 1.0)), t.intersection(&s));
+
+            let s = LineSegment::new(Vec2(0.0, 0.0), Vec2(4.0, 0.0));
+            let t = LineSegment::new(Vec2(1.0, -1.0), Vec2(1.0, 3.0));
+            assert_eq!(Some(Vec2(1.0, 0.0)), s.intersection(&t));
+
+            let s = LineSegment::new(Vec2(0.0, 0.0), Vec2(1.0, 0.0));
+            let t = LineSegment::new(Vec2(1.0, 0.0), Vec2(1.0, 1.0));
+            assert_eq!(Some(Vec2(1.0, 0.0)), s.intersection(&t));
+        }
+
+        #[test]
+        fn test_distance_to_point() {
+            let s = LineSegment::new(Vec2(0.0, 0.0), Vec2(4.0, 0.0));
+            assert_eq!(3.0, s.distance_to_point(&Vec2(2.0, 3.0)));
+            assert_eq!(3.0, s.distance_to_point(&Vec2(2.0, -3.0)));
+            assert_eq!(0.0, s.distance_to_point(&Vec2(1.0, 0.0)));
+            assert_eq!(5.0, s.distance_to_point(&Vec2(7.0, 4.0)));
+            assert_eq!(5.0, s.distance_to_point(&Vec2(-3.0, -4.0)));
+
+            let s = LineSegment::new(Vec2(1.0, 1.0), Vec2(1.0, 1.0));
+            assert_eq!(5.0, s.distance_to_point(&Vec2(4.0, 5.0)));
+        }
+
+        #[test]
+        fn test_intersection_none() {
+            let s = LineSegment::new(Vec2(0.0, 0.0), Vec2(2.0, 0.0));
+            let t = LineSegment::new(Vec2(0.0, 1.0), Vec2(2.0, 1.0));
+            assert_eq!(None, s.intersection(&t));
+
+            let s = LineSegment::new(Vec2(0.0, 0.0), Vec2(1.0, 1.0));
+            let t = LineSegment::new(Vec2(3.0, 0.0), Vec2(2.0, 1.0));
+            assert_eq!(None, s.intersection(&t));
+
+            let s = LineSegment::new(Vec2(0.0, 0.0), Vec2(2.0, 0.0));
+            let t = LineSegment::new(Vec2(1.0, 0.0), Vec2(3.0, 0.0));
+            assert_eq!(None, s.intersection(&t));
+        }
+
+        #[test]
+        fn test_intersects_inclusive() {
+            let s = LineSegment::new(IVec2(0, 0), IVec2(4, 0));
+            assert!(s.intersects_inclusive(&LineSegment::new(IVec2(2, -2), IVec2(2, 2))));
+            assert!(s.intersects_inclusive(&LineSegment::new(IVec2(2, 0), IVec2(2, 3))));
+            assert!(!s.is_crossing(&LineSegment::new(IVec2(2, 0), IVec2(2, 3))));
+            assert!(s.intersects_inclusive(&LineSegment::new(IVec2(3, 0), IVec2(6, 0))));
+            assert!(!s.is_crossing(&LineSegment::new(IVec2(3, 0), IVec2(6, 0))));
+            assert!(s.intersects_inclusive(&LineSegment::new(IVec2(4, 0), IVec2(5, 1))));
+            assert!(s.intersects_inclusive(&LineSegment::new(IVec2(-1, 0), IVec2(5, 0))));
+            assert!(!s.intersects_inclusive(&LineSegment::new(IVec2(5, 0), IVec2(6, 0))));
+            assert!(!s.intersects_inclusive(&LineSegment::new(IVec2(5, -1), IVec2(5, 1))));
+            assert!(!s.intersects_inclusive(&LineSegment::new(IVec2(0, 1), IVec2(4, 1))));
+
+            let t = LineSegment::new(Vec2(0.0, 0.0), Vec2(2.0, 2.0));
+            assert!(t.intersects_inclusive(&LineSegment::new(Vec2(2.0, 2.0), Vec2(3.0, 0.0))));
+            assert!(t.intersects_inclusive(&LineSegment::new(Vec2(1.0, 1.0), Vec2(3.0, 3.0))));
+        }
+
+        #[test]
+        fn test_contains() {
+            let s = LineSegment::new(IVec2(0, 0), IVec2(4, 2));
+            assert!(s.contains(&IVec2(0, 0)));
+            assert!(s.contains(&IVec2(4, 2)));
+            assert!(s.contains(&IVec2(2, 1)));
+            assert!(!s.contains(&IVec2(6, 3)));
+            assert!(!s.contains(&IVec2(-2, -1)));
+            assert!(!s.contains(&IVec2(2, 2)));
+
+            let t = LineSegment::new(Vec2(0.0, 0.0), Vec2(0.3, 0.9));
+            assert!(t.contains_eps(&Vec2(0.1, 0.3), EPS));
+            assert!(t.contains_eps(&Vec2(0.3, 0.9), EPS));
+            assert!(!t.contains_eps(&Vec2(0.4, 1.2), EPS));
+            assert!(!t.contains_eps(&Vec2(0.1, 0.31), EPS));
+        }
+    }
+
+    mod ccw {
+        use super::super::{Vec2, CCW, EPS};
+
+        #[test]
+        fn test_ccw() {
+            let a = Vec2(0.0, 0.0);
+            let b = Vec2(2.0, 0.0);
+            assert_eq!(CCW::CounterClockwise, CCW::ccw(a, b, Vec2(1.0, 1.0)));
+            assert_eq!(CCW::Clockwise, CCW::ccw(a, b, Vec2(1.0, -1.0)));
+            assert_eq!(CCW::CAB, CCW::ccw(a, b, Vec2(-1.0, 0.0)));
+            assert_eq!(CCW::ABC, CCW::ccw(a, b, Vec2(3.0, 0.0)));
+            assert_eq!(CCW::ACB, CCW::ccw(a, b, Vec2(1.0, 0.0)));
+        }
+
+        #[test]
+        fn test_ccw_eps() {
+            let a = Vec2(0.0, 0.0);
+            let b = Vec2(1.0, 1.0);
+            let c = Vec2(2.0, 2.0 + 1e-12);
+            let d = Vec2(0.5, 0.5 - 1e-12);
+            let e = Vec2(-1.0, -1.0 + 1e-12);
+            assert_eq!(CCW::CounterClockwise, CCW::ccw(a, b, c));
+            assert_eq!(CCW::Clockwise, CCW::ccw(a, b, d));
+            assert_eq!(CCW::ABC, CCW::ccw_eps(a, b, c, EPS));
+            assert_eq!(CCW::ACB, CCW::ccw_eps(a, b, d, EPS));
+            assert_eq!(CCW::CAB, CCW::ccw_eps(a, b, e, EPS));
+            assert_eq!(
+                CCW::CounterClockwise,
+                CCW::ccw_eps(a, b, Vec2(1.0, 2.0), EPS)
+            );
+            assert_eq!(CCW::Clockwise, CCW::ccw_eps(a, b, Vec2(2.0, 1.0), EPS));
+        }
+    }
+
+    mod ivec2 {
+        use super::super::{IVec2, Vector2D, CCW};
+
+        #[test]
+        fn test_dot_cross() {
+            assert_eq!(0, IVec2(1, 0).dot(&IVec2(0, 1)));
+            assert_eq!(-2, IVec2(1, 1).dot(&IVec2(-1, -1)));
+            assert_eq!(1, IVec2(1, 0).cross(&IVec2(0, 1)));
+            assert_eq!(2, IVec2(1, 1).cross(&IVec2(-1, 1)));
+            assert_eq!(
+                1_000_000_000_000_000_000,
+                IVec2(1_000_000_000, 0).cross(&IVec2(0, 1_000_000_000))
+            );
+        }
+
+        #[test]
+        fn test_ops() {
+            assert_eq!(IVec2(0, 0), IVec2::origin());
+            assert_eq!(IVec2(3, 1), IVec2(1, 2) + IVec2(2, -1));
+            assert_eq!(IVec2(-1, 3), IVec2(1, 2) - IVec2(2, -1));
+            assert_eq!(5.0, IVec2(3, 4).length());
+            assert_eq!(5.0, IVec2(1, 1).distance(&IVec2(4, 5)));
+        }
+
+        #[test]
+        fn test_ccw() {
+            let a = IVec2(0, 0);
+            let b = IVec2(2, 0);
+            assert_eq!(CCW::CounterClockwise, CCW::ccw(a, b, IVec2(1, 1)));
+            assert_eq!(CCW::Clockwise, CCW::ccw(a, b, IVec2(1, -1)));
+            assert_eq!(CCW::CAB, CCW::ccw(a, b, IVec2(-1, 0)));
+            assert_eq!(CCW::ABC, CCW::ccw(a, b, IVec2(3, 0)));
+            assert_eq!(CCW::ACB, CCW::ccw(a, b, IVec2(1, 0)));
+        }
+
+        #[test]
+        fn test_ccw_collinear() {
+            let a = IVec2(-123_456_789, 987_654_321);
+            let d = IVec2(99_999_989, -33_333_331);
+            for k in 1..20 {
+                let b = IVec2(a.0 + d.0 * k, a.1 + d.1 * k);
+                let c = IVec2(a.0 + d.0 * (k + 1), a.1 + d.1 * (k + 1));
+                let e = IVec2(a.0 - d.0 * k, a.1 - d.1 * k);
+                assert_eq!(CCW::ABC, CCW::ccw(a, b, c));
+                assert_eq!(CCW::ACB, CCW::ccw(a, c, b));
+                assert_eq!(CCW::CAB, CCW::ccw(a, b, e));
+            }
+            let c = IVec2(a.0 + d.0 * 3, a.1 + d.1 * 3 + 1);
+            assert_eq!(CCW::CounterClockwise, CCW::ccw(a, a + d, c));
+        }
+
+        #[test]
+        fn test_manhattan_chebyshev() {
+            assert_eq!(7, IVec2(1, 5).manhattan(&IVec2(-1, 0)));
+            assert_eq!(5, IVec2(1, 5).chebyshev(&IVec2(-1, 0)));
+        }
+    }
+
+    mod circle {
+        use super::super::{Circle, Vec2, Vector2D};
+
+        fn assert_points(expected: &[Vec2], actual: &[Vec2]) {
+            assert_eq!(expected.len(), actual.len());
+            for (e, a) in expected.iter().zip(actual) {
+                assert!(e.distance(a) < 1e-9, "{:?} != {:?}", expected, actual);
+            }
+        }
+
+        #[test]
+        fn test_intersect_line() {
+            let c = Circle::new(Vec2(0.0, 0.0), 1.0);
+            assert_points(
+                &[Vec2(-1.0, 0.0), Vec2(1.0, 0.0)],
+                &c.intersect_line(&Vec2(-2.0, 0.0), &Vec2(2.0, 0.0)),
+            );
+            assert_points(
+                &[Vec2(0.0, 1.0)],
+                &c.intersect_line(&Vec2(-2.0, 1.0), &Vec2(3.0, 1.0)),
+            );
+            assert_points(&[], &c.intersect_line(&Vec2(-2.0, 2.0), &Vec2(3.0, 2.0)));
+
+            let c = Circle::new(Vec2(1.0, 1.0), 5.0);
+            assert_points(
+                &[Vec2(1.0, -4.0), Vec2(1.0, 6.0)],
+                &c.intersect_line(&Vec2(1.0, 0.0), &Vec2(1.0, 1.0)),
+            );
+        }
+
+        #[test]
+        fn test_intersect_circle() {
+            let c = Circle::new(Vec2(0.0, 0.0), 5.0);
+            assert_points(
+                &[Vec2(4.0, -3.0), Vec2(4.0, 3.0)],
+                &c.intersect_circle(&Circle::new(Vec2(8.0, 0.0), 5.0)),
+            );
+            assert_points(
+                &[Vec2(5.0, 0.0)],
+                &c.intersect_circle(&Circle::new(Vec2(8.0, 0.0), 3.0)),
+            );
+            assert_points(
+                &[Vec2(0.0, 5.0)],
+                &c.intersect_circle(&Circle::new(Vec2(0.0, 3.0), 2.0)),
+            );
+            assert_points(&[], &c.intersect_circle(&Circle::new(Vec2(9.0, 0.0), 3.0)));
+            assert_points(&[], &c.intersect_circle(&Circle::new(Vec2(1.0, 0.0), 1.0)));
+            assert_points(&[], &c.intersect_circle(&Circle::new(Vec2(0.0, 0.0), 3.0)));
+        }
+    }
+
+    mod turn {
+        use super::super::{IVec2, Turn, Vec2, Vector2D};
+        use std::cmp::Ordering;
+
+        #[test]
+        fn test_cross_sign() {
+            assert_eq!(Ordering::Greater, IVec2(1, 0).cross_sign(&IVec2(0, 1)));
+            assert_eq!(Ordering::Less, IVec2(0, 1).cross_sign(&IVec2(1, 0)));
+            assert_eq!(Ordering::Equal, IVec2(2, 4).cross_sign(&IVec2(-1, -2)));
+            assert_eq!(
+                Ordering::Equal,
+                Vec2(0.1, 0.2).cross_sign(&Vec2(0.3, 0.6000000000000001))
+            );
+            assert_eq!(
+                Ordering::Greater,
+                Vec2(1.0, 0.0).cross_sign(&Vec2(1.0, 1e-6))
+            );
+        }
+
+        #[test]
+        fn test_turn() {
+            let (a, b) = (IVec2(0, 0), IVec2(2, 0));
+            assert_eq!(Turn::Left, Turn::of(&a, &b, &IVec2(3, 1)));
+            assert_eq!(Turn::Right, Turn::of(&a, &b, &IVec2(3, -1)));
+            assert_eq!(Turn::Straight, Turn::of(&a, &b, &IVec2(5, 0)));
+            assert_eq!(Turn::Straight, Turn::of(&a, &b, &IVec2(-1, 0)));
+
+            let (a, b) = (Vec2(0.0, 0.0), Vec2(0.1, 0.1));
+            assert_eq!(Turn::Straight, Turn::of(&a, &b, &Vec2(0.3, 0.3)));
+            assert_eq!(Turn::Straight, Turn::of(&a, &b, &Vec2(0.3, 0.3 + 1e-12)));
+            assert_eq!(Turn::Left, Turn::of(&a, &b, &Vec2(0.3, 0.31)));
+            assert_eq!(Turn::Right, Turn::of(&a, &b, &Vec2(0.31, 0.3)));
+        }
     }
 }